@@ -0,0 +1,200 @@
+use crate::ftw_error::FtwError;
+use crate::ftw_scene::FtwScene;
+use liquid::{object, Object};
+use std::path::Path;
+use voca_rs::Voca;
+
+pub const BINDINGS_BEGIN: &str = "// ftw bindings begin";
+pub const BINDINGS_END: &str = "// ftw bindings end";
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in",
+    "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield", "union",
+];
+
+/// A typed accessor of a child node of a scene
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwBinding {
+    pub path: String,
+    pub node_type: String,
+    pub function_name: String,
+}
+
+impl FtwBinding {
+    /// The bindings of every node of `scene` except the root node, the types
+    /// are resolved with [`FtwScene::get_node_type`] in the project at `root`
+    /// and the accessors that would have the same name get a numeric suffix
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the scene of an instanced or overridden node cannot be found or it has no such node
+    pub fn get_bindings(root: &Path, scene: &FtwScene) -> Result<Vec<Self>, FtwError> {
+        scene
+            .get_nodes()
+            .iter()
+            .filter(|node| node.parent.is_some())
+            .try_fold(vec![], |mut bindings: Vec<FtwBinding>, node| {
+                let path = node.get_path();
+                let function_name = FtwBinding::get_function_name(&path);
+                let unique_function_name = (1..)
+                    .map(|index| match index {
+                        1 => function_name.clone(),
+                        _ => format!("{function_name}_{index}"),
+                    })
+                    .find(|unique_function_name| {
+                        !bindings
+                            .iter()
+                            .any(|binding| binding.function_name == *unique_function_name)
+                    })
+                    .unwrap_or(function_name);
+                bindings.push(FtwBinding {
+                    path,
+                    node_type: scene.get_node_type(root, node)?,
+                    function_name: unique_function_name,
+                });
+                Ok(bindings)
+            })
+    }
+
+    /// The name of the accessor of the node at `path`, `Sprite/Anim` becomes
+    /// `sprite_anim`, names that are not identifiers get a `node` prefix or
+    /// suffix and names without a single identifier character (e.g. `!!!`)
+    /// become `node`
+    #[must_use]
+    pub fn get_function_name(path: &str) -> String {
+        let function_name = path
+            .split('/')
+            .map(|name| name._snake_case())
+            .filter(|name| !name.is_empty())
+            .collect::<Vec<String>>()
+            .join("_");
+        let is_identifier = function_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && function_name.chars().any(|c| c.is_ascii_alphanumeric());
+        if !is_identifier {
+            String::from("node")
+        } else if function_name.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("node_{function_name}")
+        } else if KEYWORDS.contains(&function_name.as_str()) {
+            format!("{function_name}_node")
+        } else {
+            function_name
+        }
+    }
+
+    /// The globals of the binding in the bindings template
+    #[must_use]
+    pub fn to_tmpl_object(&self) -> Object {
+        object!({
+            "path": self.path,
+            "node_type": self.node_type,
+            "function_name": self.function_name,
+        })
+    }
+
+    /// Replaces the bindings between the markers in `contents`, the bindings
+    /// are appended when there are no markers yet
+    #[must_use]
+    pub fn replace_bindings(contents: &str, bindings: &str) -> String {
+        let bindings = bindings.trim_end();
+        let markers = contents.find(BINDINGS_BEGIN).and_then(|begin| {
+            let end = contents[begin..].find(BINDINGS_END)? + begin + BINDINGS_END.len();
+            Some((begin, end))
+        });
+        match markers {
+            Some((begin, end)) => format!("{}{bindings}{}", &contents[..begin], &contents[end..]),
+            None => format!("{}\n\n{bindings}\n", contents.trim_end()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod ftw_bindings_tests {
+    use super::*;
+
+    #[test]
+    fn test_get_bindings() -> Result<(), FtwError> {
+        let scene: FtwScene = r#"[gd_scene format=2]
+
+[node name="Boss" type="KinematicBody2D"]
+
+[node name="Sprite" type="Sprite" parent="."]
+
+[node name="Anim" type="AnimationPlayer" parent="Sprite"]
+
+[node name="Sprite_Anim" type="Sprite" parent="."]
+
+[node name="!!!" type="Node2D" parent="."]
+
+[node name="???" type="Node2D" parent="."]
+"#
+        .parse()?;
+        assert_eq!(
+            vec![
+                FtwBinding {
+                    path: "Sprite".to_string(),
+                    node_type: "Sprite".to_string(),
+                    function_name: "sprite".to_string(),
+                },
+                FtwBinding {
+                    path: "Sprite/Anim".to_string(),
+                    node_type: "AnimationPlayer".to_string(),
+                    function_name: "sprite_anim".to_string(),
+                },
+                FtwBinding {
+                    path: "Sprite_Anim".to_string(),
+                    node_type: "Sprite".to_string(),
+                    function_name: "sprite_anim_2".to_string(),
+                },
+                FtwBinding {
+                    path: "!!!".to_string(),
+                    node_type: "Node2D".to_string(),
+                    function_name: "node".to_string(),
+                },
+                FtwBinding {
+                    path: "???".to_string(),
+                    node_type: "Node2D".to_string(),
+                    function_name: "node_2".to_string(),
+                },
+            ],
+            FtwBinding::get_bindings(Path::new("."), &scene)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_function_name() {
+        let get_function_name = FtwBinding::get_function_name;
+        assert_eq!("sprite_anim", get_function_name("Sprite/Anim"));
+        assert_eq!("hit_box", get_function_name("Hit Box"));
+        assert_eq!("move_node", get_function_name("Move"));
+        assert_eq!("node_2d", get_function_name("2D"));
+        assert_eq!("node", get_function_name("!!!"));
+        assert_eq!("node", get_function_name("日本"));
+        assert_eq!("sprite", get_function_name("Sprite/???"));
+    }
+
+    #[test]
+    fn test_replace_bindings() {
+        let bindings = format!("{BINDINGS_BEGIN}\npub mod bindings {{}}\n{BINDINGS_END}\n");
+        let contents = "pub struct Boss;\n";
+        let contents_with_bindings = FtwBinding::replace_bindings(contents, &bindings);
+        assert_eq!(
+            format!(
+                "pub struct Boss;\n\n{BINDINGS_BEGIN}\npub mod bindings {{}}\n{BINDINGS_END}\n"
+            ),
+            contents_with_bindings
+        );
+        let new_bindings =
+            format!("{BINDINGS_BEGIN}\npub mod bindings {{ fn sprite() {{}} }}\n{BINDINGS_END}\n");
+        let contents = format!("{contents_with_bindings}\nfn after() {{}}\n");
+        assert_eq!(
+            format!("pub struct Boss;\n\n{BINDINGS_BEGIN}\npub mod bindings {{ fn sprite() {{}} }}\n{BINDINGS_END}\n\nfn after() {{}}\n"),
+            FtwBinding::replace_bindings(&contents, &new_bindings)
+        );
+    }
+}
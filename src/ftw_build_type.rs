@@ -4,8 +4,9 @@ use crate::type_alias::{CliArg, ExportArg};
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
+use strum_macros::EnumIter;
 
-#[derive(Clone, Copy, Default, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Default, Debug, EnumIter, Eq, PartialEq)]
 pub enum FtwBuildType {
     #[default]
     Debug,
@@ -0,0 +1,143 @@
+use crate::ftw_error::FtwError;
+use crate::ftw_target::FtwTarget;
+use crate::traits::ToCliArg;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::Path;
+use strum::IntoEnumIterator;
+
+pub const TAG_FILE: &str = ".tag";
+
+const PROJECT_FILES: [&str; 8] = [
+    "Cargo.toml",
+    "Makefile",
+    "godot/default_env.tres",
+    "godot/export_presets.cfg",
+    "godot/native/game.gdnlib",
+    "godot/project.godot",
+    "rust/src/lib.rs",
+    "rust/Cargo.toml",
+];
+
+/// The required paths that are missing from a project and the template
+/// version (from `.tag`) the project was created with
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FtwCheck {
+    pub missing: Vec<String>,
+    pub tag: Option<String>,
+}
+
+impl FtwCheck {
+    #[must_use]
+    pub fn new(root: &Path) -> Self {
+        let targets = FtwTarget::iter().flat_map(|target| {
+            let target_cli_arg = target.to_cli_arg();
+            let gitkeep = format!("{target_cli_arg}/.gitkeep");
+            [format!("bin/{gitkeep}"), format!("lib/{gitkeep}")]
+        });
+        let missing = PROJECT_FILES
+            .iter()
+            .map(ToString::to_string)
+            .chain(targets)
+            .filter(|path| !FtwCheck::exists(root, path))
+            .collect();
+        let tag = read_to_string(root.join(TAG_FILE))
+            .ok()
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty());
+        FtwCheck { missing, tag }
+    }
+
+    fn exists(root: &Path, path: &str) -> bool {
+        // TODO: Remove the check for the Makefile in the future
+        if path == "Makefile" {
+            root.join(path).exists() || root.join("Makefile.toml").exists()
+        } else {
+            root.join(path).exists()
+        }
+    }
+
+    fn is_fixable(path: &str) -> bool {
+        (path.starts_with("bin/") || path.starts_with("lib/")) && path.ends_with("/.gitkeep")
+    }
+
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Recreates the missing target directories and their `.gitkeep` files,
+    /// returns the recreated paths, the other missing paths are kept
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if a directory or a `.gitkeep` file cannot be created
+    pub fn fix(&mut self, root: &Path) -> Result<Vec<String>, FtwError> {
+        let (fixable, missing): (Vec<String>, Vec<String>) = self
+            .missing
+            .drain(..)
+            .partition(|path| FtwCheck::is_fixable(path));
+        self.missing = missing;
+        for path in &fixable {
+            let path = root.join(path);
+            create_dir_all(path.parent().ok_or(FtwError::PathError)?)?;
+            write(path, "")?;
+        }
+        Ok(fixable)
+    }
+}
+
+#[cfg(test)]
+mod ftw_check_tests {
+    use super::*;
+    use crate::test_util::create_project_skeleton;
+    use std::env;
+    use std::fs::{remove_dir_all, remove_file};
+
+    #[test]
+    fn test_new() -> Result<(), FtwError> {
+        let root = env::temp_dir().join(format!("ftw-check-{}", std::process::id()));
+        create_dir_all(&root)?;
+        let check = FtwCheck::new(&root);
+        assert_eq!(8 + FtwTarget::iter().count() * 2, check.missing.len());
+        assert_eq!(None, check.tag);
+        create_project_skeleton(&root)?;
+        write(root.join(TAG_FILE), "v1.5.0\n")?;
+        let check = FtwCheck::new(&root);
+        assert!(check.is_valid());
+        assert_eq!(Some("v1.5.0".to_string()), check.tag);
+        remove_file(root.join("Makefile.toml"))?;
+        write(root.join("Makefile"), "")?;
+        assert!(FtwCheck::new(&root).is_valid());
+        remove_dir_all(&root)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_fix() -> Result<(), FtwError> {
+        let root = env::temp_dir().join(format!("ftw-check-fix-{}", std::process::id()));
+        create_project_skeleton(&root)?;
+        remove_dir_all(root.join("bin/x86_64-unknown-linux-gnu"))?;
+        remove_file(root.join("lib/aarch64-linux-android/.gitkeep"))?;
+        remove_file(root.join("rust/src/lib.rs"))?;
+        let mut check = FtwCheck::new(&root);
+        assert_eq!(
+            vec![
+                "rust/src/lib.rs".to_string(),
+                "lib/aarch64-linux-android/.gitkeep".to_string(),
+                "bin/x86_64-unknown-linux-gnu/.gitkeep".to_string(),
+            ],
+            check.missing
+        );
+        assert_eq!(
+            vec![
+                "lib/aarch64-linux-android/.gitkeep".to_string(),
+                "bin/x86_64-unknown-linux-gnu/.gitkeep".to_string(),
+            ],
+            check.fix(&root)?
+        );
+        assert_eq!(vec!["rust/src/lib.rs".to_string()], check.missing);
+        assert_eq!(check, FtwCheck::new(&root));
+        remove_dir_all(&root)?;
+        Ok(())
+    }
+}
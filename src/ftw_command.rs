@@ -1,13 +1,30 @@
+use crate::ftw_bindings::FtwBinding;
 use crate::ftw_build_type::FtwBuildType;
+use crate::ftw_check::{FtwCheck, TAG_FILE};
 use crate::ftw_configuration::FtwConfiguration;
+use crate::ftw_container::FtwContainerSession;
 use crate::ftw_error::FtwError;
+use crate::ftw_event::{FtwEvent, FtwListener, FtwPrinter};
+use crate::ftw_gdnlib::{FtwGdnlib, FtwGdnlibSwitch, GDNLIB_FILE};
+use crate::ftw_instance::FtwInstance;
+use crate::ftw_keystore::FtwKeystore;
 use crate::ftw_machine_type::FtwMachineType;
+use crate::ftw_manifest::{FtwArtifactStatus, FtwManifest, FtwManifestKind};
 use crate::ftw_node_type::FtwNodeType;
+use crate::ftw_package::FtwPackage;
+use crate::ftw_presets::{FtwPresets, EXPORT_PRESETS_FILE};
+use crate::ftw_property::FtwProperty;
+use crate::ftw_run_options::FtwRunOptions;
+use crate::ftw_scene::FtwScene;
+use crate::ftw_signal::FtwSignal;
 use crate::ftw_success::FtwSuccess;
 use crate::ftw_tag::FtwTag;
 use crate::ftw_target::FtwTarget;
 use crate::ftw_template::FtwTemplate;
-use crate::traits::{Compiler, Processor, Runner, ToCliArg, ToGitTag, ToGitUrl};
+use crate::ftw_test_report::{FtwTestReport, FtwTestStatus, FtwTestSuite};
+use crate::ftw_upgrade::{FtwUpgrade, FtwUpgradeChange, UNTAGGED_TEMPLATE_TAG};
+use crate::ftw_watcher::FtwWatcher;
+use crate::traits::{Compiler, Processor, Runner, ToCliArg, ToExportName, ToGitTag, ToGitUrl};
 use crate::type_alias::{ClassName, FtwResult, ProjectName};
 use crate::util;
 
@@ -17,14 +34,25 @@ use fs_extra::remove_items;
 use kstring::KStringBase;
 use liquid::{object, Object, ParserBuilder};
 use liquid_core::model::{ScalarCow, Value};
+use nanoid::nanoid;
 use regex::Regex;
-use std::fs::{create_dir_all, read_dir, write, File, OpenOptions};
+use std::convert::Infallible;
+use std::env;
+use std::fs::{create_dir_all, read_dir, read_to_string, write, File, OpenOptions};
 use std::io::prelude::*;
-use std::path::Path;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::rc::Rc;
+use std::sync::{Mutex, Once};
+use std::time::{Duration, Instant};
 use std::{thread, time};
-use strum::IntoEnumIterator;
 use voca_rs::Voca;
 use walkdir::WalkDir;
+use zip::ZipArchive;
+
+static WATCHED_GAME: Mutex<Option<Child>> = Mutex::new(None);
+static CTRL_C_HANDLER: Once = Once::new();
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum FtwCommand {
@@ -36,12 +64,30 @@ pub enum FtwCommand {
     Class {
         class_name: ClassName,
         node_type: FtwNodeType,
+        properties: Vec<String>,
+        signals: Vec<String>,
+        extends_scene: Option<String>,
+        children: Vec<String>,
     },
     Singleton {
         class_name: ClassName,
     },
+    Resource {
+        class_name: ClassName,
+        properties: Vec<String>,
+    },
+    Attach {
+        scene: String,
+    },
+    Bindings {
+        class_name: ClassName,
+    },
     Run {
         machine_type: FtwMachineType,
+        build_type: FtwBuildType,
+        exported: Option<FtwTarget>,
+        options: FtwRunOptions,
+        instances: Vec<FtwInstance>,
     },
     Build {
         targets: Vec<FtwTarget>,
@@ -51,16 +97,128 @@ pub enum FtwCommand {
         targets: Vec<FtwTarget>,
         build_type: FtwBuildType,
     },
+    Package {
+        targets: Vec<FtwTarget>,
+        build_type: FtwBuildType,
+    },
+    PresetsSync,
+    AndroidKeystore {
+        build_type: FtwBuildType,
+    },
+    Watch {
+        target: FtwTarget,
+        build_type: FtwBuildType,
+        run: bool,
+    },
+    Test {
+        runner: Option<String>,
+        junit: Option<String>,
+    },
+    Check {
+        fix: bool,
+    },
+    Upgrade {
+        template: FtwTemplate,
+        tag: FtwTag,
+    },
+    Artifacts,
     Clean,
 }
 
 #[rustfmt::skip::macros(cmd, format)]
 impl FtwCommand {
+    /// Prefixes the class name of `ftw class`, `ftw singleton`,
+    /// `ftw resource` and `ftw bindings` with `directories`, so that `Goblin`
+    /// becomes `enemies/Goblin`
+    #[must_use]
+    pub fn with_class_directories(self, directories: &[String]) -> Self {
+        let prefix = |class_name: ClassName| {
+            let mut class_path = directories.to_vec();
+            class_path.push(class_name);
+            class_path.join("/")
+        };
+        match self {
+            FtwCommand::Class {
+                class_name,
+                node_type,
+                properties,
+                signals,
+                extends_scene,
+                children,
+            } => FtwCommand::Class {
+                class_name: prefix(class_name),
+                node_type,
+                properties,
+                signals,
+                extends_scene,
+                children,
+            },
+            FtwCommand::Singleton { class_name } => FtwCommand::Singleton {
+                class_name: prefix(class_name),
+            },
+            FtwCommand::Bindings { class_name } => FtwCommand::Bindings {
+                class_name: prefix(class_name),
+            },
+            FtwCommand::Resource {
+                class_name,
+                properties,
+            } => FtwCommand::Resource {
+                class_name: prefix(class_name),
+                properties,
+            },
+            command => command,
+        }
+    }
+
+    /// Makes the scene of `ftw attach` and `ftw class --extends-scene` and the
+    /// report of `ftw test --junit` that were given relative to `current_dir`
+    /// relative to `root`, class names and `res://` paths are left as they are
+    #[must_use]
+    pub fn with_paths_relative_to(self, current_dir: &Path, root: &Path) -> Self {
+        let resolve_scene = |scene: String| {
+            let is_scene_file = Path::new(&scene)
+                .extension()
+                .is_some_and(|ext| ext == "tscn");
+            if is_scene_file && !scene.starts_with("res://") {
+                util::get_path_relative_to(current_dir, root, &scene)
+            } else {
+                scene
+            }
+        };
+        match self {
+            FtwCommand::Class {
+                class_name,
+                node_type,
+                properties,
+                signals,
+                extends_scene,
+                children,
+            } => FtwCommand::Class {
+                class_name,
+                node_type,
+                properties,
+                signals,
+                extends_scene: extends_scene.map(resolve_scene),
+                children,
+            },
+            FtwCommand::Attach { scene } => FtwCommand::Attach {
+                scene: resolve_scene(scene),
+            },
+            FtwCommand::Test { runner, junit } => FtwCommand::Test {
+                runner,
+                junit: junit.map(|junit| util::get_path_relative_to(current_dir, root, &junit)),
+            },
+            command => command,
+        }
+    }
+
     fn generate_project(
         project_name: &str,
         template: &FtwTemplate,
         tag: &FtwTag,
-    ) -> Result<(), FtwError> {
+        destination: Option<PathBuf>,
+        vcs: Vcs,
+    ) -> Result<PathBuf, FtwError> {
         let git_url = &template.to_git_url();
         let git_tag = &tag.to_git_tag();
         let template_path = TemplatePath {
@@ -82,26 +240,32 @@ impl FtwCommand {
             list_favorites: false,
             silent: true,
             template_values_file: None,
-            vcs: Some(Vcs::Git),
+            vcs: Some(vcs),
             bin: false,
             lib: true,
             ssh_identity: None,
             define: vec![],
             init: false,
-            destination: None,
+            destination,
             force_git_init: false,
             allow_commands: false,
             overwrite: false,
             other_args: None,
         };
-        generate(generate_args)?;
-        Ok(())
+        Ok(generate(generate_args)?)
     }
 
     fn append_to_gitignore(project_name: &str) -> Result<(), FtwError> {
         let gitignore_path: String = format!("{project_name}/.gitignore");
         let mut gitignore_file = OpenOptions::new().append(true).open(gitignore_path)?;
-        let things_to_be_ignored = [".ftw", "bin/*", "godot/export_presets.cfg", "lib/*", ".tag"];
+        let things_to_be_ignored = [
+            ".ftw",
+            "bin/*",
+            "godot/export_presets.cfg",
+            "lib/*",
+            ".tag",
+            "*.keystore",
+        ];
         for thing in things_to_be_ignored {
             writeln!(gitignore_file, "{thing}")?;
         }
@@ -116,56 +280,45 @@ impl FtwCommand {
         Ok(remove_items(&files_to_be_removed)?)
     }
 
+    /// Renders the template into `target_file_path` relative to `root`
     fn create_file(
+        root: &Path,
         template_contents: &str,
         target_file_path: &str,
         template_globals: &Object,
+        listener: &dyn FtwListener,
     ) -> Result<(), FtwError> {
         let builder = ParserBuilder::with_stdlib().build()?;
         let template = builder.parse(template_contents)?;
         let output = template.render(template_globals)?;
-        write(target_file_path, output.as_bytes())?;
-        println!("{target_file_path} has been created...");
+        write(root.join(target_file_path), output.as_bytes())?;
+        listener.on_event(&FtwEvent::FileCreated(target_file_path.to_string()));
         Ok(())
     }
 
-    fn is_valid_project() -> Result<bool, FtwError> {
-        let project_files = [
-            "Cargo.toml",
-            "Makefile",
-            "godot/default_env.tres",
-            "godot/export_presets.cfg",
-            "godot/native/game.gdnlib",
-            "godot/project.godot",
-            "rust/src/lib.rs",
-            "rust/Cargo.toml",
-        ];
-        let targets: Vec<String> = FtwTarget::iter()
-            .flat_map(|target| {
-                let target_cli_arg = target.to_cli_arg();
-                let gitkeep = format!("{target_cli_arg}/.gitkeep");
-                let bin_gitkeep = format!("bin/{gitkeep}");
-                let lib_gitkeep = format!("lib/{gitkeep}");
-                [bin_gitkeep, lib_gitkeep]
-            })
-            .collect();
-        let is_valid_project = project_files.iter().all(|project_file| {
-            // TODO: Remove the check for the Makefile in the future
-            if project_file == &"Makefile" {
-                Path::new(project_file).exists() || Path::new("Makefile.toml").exists()
-            } else {
-                Path::new(project_file).exists()
-            }
-        });
-        let is_valid_targets = targets.iter().all(|target| Path::new(&target).exists());
-        if is_valid_project && is_valid_targets {
-            println!("Project is valid...");
+    pub(crate) fn is_valid_project(
+        root: &Path,
+        listener: &dyn FtwListener,
+    ) -> Result<bool, FtwError> {
+        let check = FtwCheck::new(root);
+        if check.is_valid() {
+            listener.on_event(&FtwEvent::ProjectValidated);
             Ok(true)
         } else {
-            Err(FtwError::InvalidProject)
+            Err(FtwError::InvalidProject(check.missing))
         }
     }
 
+    /// Walks up from `directory` and returns the nearest directory that is the
+    /// root of a valid project
+    #[must_use]
+    pub fn find_project_root(directory: &Path) -> Option<PathBuf> {
+        directory
+            .ancestors()
+            .find(|ancestor| FtwCheck::new(ancestor).is_valid())
+            .map(Path::to_path_buf)
+    }
+
     /// # Errors
     ///
     /// Will return `Err` if the regular expression is invalid
@@ -174,16 +327,16 @@ impl FtwCommand {
         Ok(reg_ex.find(contents).is_some())
     }
 
-    fn get_classes_from_directory(directory: &str) -> Result<String, FtwError> {
+    fn get_classes_from_directory(root: &Path, directory: &str) -> Result<String, FtwError> {
         let mut classes: Vec<String> = Vec::new();
-        for entry in WalkDir::new(directory) {
+        for entry in WalkDir::new(root.join(directory)) {
             let entry = entry?;
             let entry_path = entry.path();
-            let path = Path::new(&entry_path);
-            let is_file = path.is_file();
+            let path = entry_path.strip_prefix(root).unwrap_or(entry_path);
+            let is_file = entry_path.is_file();
             if is_file {
                 let mut file_contents = String::new();
-                let mut file = File::open(entry.path())?;
+                let mut file = File::open(entry_path)?;
                 file.read_to_string(&mut file_contents)?;
                 let is_native_class = FtwCommand::is_derving_native_class(&file_contents)?;
                 if is_native_class {
@@ -211,28 +364,39 @@ impl FtwCommand {
         object!({ "class_name": class_name, "node_type": node_type.to_string() })
     }
 
-    fn create_lib_rs_file(class_name: &str, node_type: FtwNodeType) -> Result<(), FtwError> {
+    pub(crate) fn create_lib_rs_file(
+        root: &Path,
+        class_name: &str,
+        node_type: FtwNodeType,
+        listener: &dyn FtwListener,
+    ) -> Result<(), FtwError> {
         let mut tmpl_globals = FtwCommand::get_tmpl_globals(class_name, node_type);
-        let modules = FtwCommand::get_modules_from_directory("rust/src")?;
+        let modules = FtwCommand::get_modules_from_directory(&root.join("rust/src"))?;
         let k = KStringBase::from_ref("modules");
         let v = Value::Scalar(ScalarCow::from(modules));
         tmpl_globals.insert(k, v);
-        let classes = FtwCommand::get_classes_from_directory("rust/src")?;
+        let classes = FtwCommand::get_classes_from_directory(root, "rust/src")?;
         let k = KStringBase::from_ref("classes");
         let v = Value::Scalar(ScalarCow::from(classes));
         tmpl_globals.insert(k, v);
         let template = &String::from_utf8_lossy(include_bytes!("templates/lib_tmpl.rs"));
-        FtwCommand::create_file(template, "rust/src/lib.rs", &tmpl_globals)
+        FtwCommand::create_file(root, template, "rust/src/lib.rs", &tmpl_globals, listener)
     }
 
-    fn create_directory(base_path: &str, directories: &[String]) -> Result<String, FtwError> {
+    /// Creates the directories inside `base_path` relative to `root`, returns
+    /// the path of the innermost directory relative to `root`
+    fn create_directory(
+        root: &Path,
+        base_path: &str,
+        directories: &[String],
+    ) -> Result<String, FtwError> {
         let dir_path = directories.join("/");
         let full_path = format!("{base_path}/{dir_path}");
-        create_dir_all(&full_path)?;
+        create_dir_all(root.join(&full_path))?;
         Ok(full_path)
     }
 
-    fn get_modules_from_directory(directory: &str) -> Result<String, FtwError> {
+    fn get_modules_from_directory(directory: &Path) -> Result<String, FtwError> {
         let files_and_folders = read_dir(directory)?;
         let mut modules: Vec<String> = Vec::new();
         for entry in files_and_folders {
@@ -242,7 +406,7 @@ impl FtwCommand {
             let is_file = path.is_file();
             if is_file {
                 let mut file_contents = String::new();
-                let mut file = File::open(&entry.path())?;
+                let mut file = File::open(entry.path())?;
                 file.read_to_string(&mut file_contents)?;
                 let is_native_class = FtwCommand::is_derving_native_class(&file_contents)?;
                 if is_native_class {
@@ -274,166 +438,1072 @@ impl FtwCommand {
         Ok(modules.join("|"))
     }
 
-    fn create_mod_rs_file(base_src_path: &str, directories: &[String]) -> Result<(), FtwError> {
+    fn create_mod_rs_file(
+        root: &Path,
+        base_src_path: &str,
+        directories: &[String],
+        listener: &dyn FtwListener,
+    ) -> Result<(), FtwError> {
         if directories.is_empty() {
             Ok(())
         } else {
             let dir = directories.join("/");
             let current_path = format!("{base_src_path}/{dir}");
             let mod_rs_file = format!("{current_path}/mod.rs");
-            let modules = FtwCommand::get_modules_from_directory(&current_path)?;
+            let modules = FtwCommand::get_modules_from_directory(&root.join(&current_path))?;
             let tmpl_globals = object!({ "modules": modules });
             let template = &String::from_utf8_lossy(include_bytes!("templates/mod_tmpl.rs"));
-            FtwCommand::create_file(template, &mod_rs_file, &tmpl_globals)?;
+            FtwCommand::create_file(root, template, &mod_rs_file, &tmpl_globals, listener)?;
             match directories.split_last() {
-                Some((_, init)) => FtwCommand::create_mod_rs_file(base_src_path, init),
+                Some((_, init)) => {
+                    FtwCommand::create_mod_rs_file(root, base_src_path, init, listener)
+                }
                 _ => unreachable!(),
             }
         }
     }
 
-    fn create_class_rs_file(
+    pub(crate) fn create_class_rs_file(
+        root: &Path,
         class_name: &str,
         directories: &[String],
         node_type: FtwNodeType,
+        properties: &[FtwProperty],
+        signals: &[FtwSignal],
+        listener: &dyn FtwListener,
     ) -> Result<(), FtwError> {
         let base_src_path = "rust/src";
-        let src_dir_path = FtwCommand::create_directory(base_src_path, directories)?;
+        let src_dir_path = FtwCommand::create_directory(root, base_src_path, directories)?;
         let class_name_snake_case = class_name._snake_case();
         let class_rs_file = format!("{src_dir_path}/{class_name_snake_case}.rs");
-        if !Path::new(&class_rs_file).exists() {
-            let tmpl_globals = FtwCommand::get_tmpl_globals(class_name, node_type);
+        if !root.join(&class_rs_file).exists() {
+            let tmpl_globals =
+                FtwCommand::get_class_tmpl_globals(class_name, node_type, properties, signals);
             let template = &String::from_utf8_lossy(include_bytes!("templates/class_tmpl.rs"));
-            FtwCommand::create_file(template, &class_rs_file, &tmpl_globals)?;
+            FtwCommand::create_file(root, template, &class_rs_file, &tmpl_globals, listener)?;
         }
-        FtwCommand::create_mod_rs_file(base_src_path, directories)?;
+        FtwCommand::create_mod_rs_file(root, base_src_path, directories, listener)?;
         Ok(())
     }
 
-    fn create_gdns_file(
+    pub(crate) fn create_gdns_file(
+        root: &Path,
         class_name: &str,
         directories: &[String],
         node_type: FtwNodeType,
+        listener: &dyn FtwListener,
     ) -> Result<(), FtwError> {
-        let gdns_dir_path = FtwCommand::create_directory("godot/native", directories)?;
+        let gdns_dir_path = FtwCommand::create_directory(root, "godot/native", directories)?;
         let class_name_pascal_case = class_name._pascal_case();
         let gdns_file = format!("{gdns_dir_path}/{class_name_pascal_case}.gdns");
-        if !Path::new(&gdns_file).exists() {
+        if !root.join(&gdns_file).exists() {
             let tmpl_globals = FtwCommand::get_tmpl_globals(class_name, node_type);
             let template = &String::from_utf8_lossy(include_bytes!("templates/gdns_tmpl.gdns"));
-            FtwCommand::create_file(template, &gdns_file, &tmpl_globals)?;
+            FtwCommand::create_file(root, template, &gdns_file, &tmpl_globals, listener)?;
         }
         Ok(())
     }
 
-    fn create_tscn_file(
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn create_tscn_file(
+        root: &Path,
         class_name: &str,
         directories: &[String],
         node_type: FtwNodeType,
+        properties: &[FtwProperty],
+        base_scene: Option<&str>,
+        children: &[FtwNodeType],
+        listener: &dyn FtwListener,
     ) -> Result<(), FtwError> {
-        let tscn_dir_path = FtwCommand::create_directory("godot/scenes", directories)?;
+        let tscn_dir_path = FtwCommand::create_directory(root, "godot/scenes", directories)?;
         let class_name_pascal_case = class_name._pascal_case();
         let tscn_file = format!("{tscn_dir_path}/{class_name_pascal_case}.tscn");
-        if !Path::new(&tscn_file).exists() {
-            let mut tmpl_globals = FtwCommand::get_tmpl_globals(class_name, node_type);
+        if !root.join(&tscn_file).exists() {
+            let mut tmpl_globals =
+                FtwCommand::get_class_tmpl_globals(class_name, node_type, properties, &[]);
             let k = KStringBase::from_ref("dir_path");
-            let v = Value::Scalar(ScalarCow::from(if directories.is_empty() {
-                String::new()
-            } else {
-                let mut dir = directories.join("/");
-                dir.push('/');
-                dir
-            }));
+            let v = Value::Scalar(ScalarCow::from(FtwCommand::get_dir_path(directories)));
+            tmpl_globals.insert(k, v);
+            let k = KStringBase::from_ref("has_base_scene");
+            let v = Value::Scalar(ScalarCow::from(base_scene.is_some()));
+            tmpl_globals.insert(k, v);
+            let k = KStringBase::from_ref("base_scene");
+            let v = Value::Scalar(ScalarCow::from(base_scene.unwrap_or_default().to_string()));
+            tmpl_globals.insert(k, v);
+            // the script and the base scene are the only external resources
+            let load_steps: i64 = if base_scene.is_some() { 3 } else { 2 };
+            let k = KStringBase::from_ref("load_steps");
+            let v = Value::Scalar(ScalarCow::from(load_steps));
+            tmpl_globals.insert(k, v);
+            let k = KStringBase::from_ref("children");
+            let v = Value::Array(
+                FtwCommand::get_child_names(children)
+                    .iter()
+                    .zip(children)
+                    .map(|(name, node_type)| {
+                        Value::Object(object!({ "name": name, "node_type": node_type.to_string() }))
+                    })
+                    .collect(),
+            );
             tmpl_globals.insert(k, v);
             let template = &String::from_utf8_lossy(include_bytes!("templates/tscn_tmpl.tscn"));
-            FtwCommand::create_file(template, &tscn_file, &tmpl_globals)?;
+            FtwCommand::create_file(root, template, &tscn_file, &tmpl_globals, listener)?;
+        }
+        Ok(())
+    }
+
+    /// Parses a node type without falling back to `Node`, `None` if it is not
+    /// a godot node type
+    fn parse_node_type(node_type: &str) -> Option<FtwNodeType> {
+        node_type
+            .parse()
+            .ok()
+            .filter(|parsed: &FtwNodeType| parsed.to_string() == node_type)
+    }
+
+    /// The names of the child nodes, named after their type like the godot
+    /// editor does (`Sprite`, `Sprite2`...)
+    fn get_child_names(children: &[FtwNodeType]) -> Vec<String> {
+        children.iter().fold(vec![], |mut names, child| {
+            let name = child.to_string();
+            let unique_name = (1..)
+                .map(|index| match index {
+                    1 => name.clone(),
+                    _ => format!("{name}{index}"),
+                })
+                .find(|unique_name| !names.contains(unique_name))
+                .unwrap_or(name);
+            names.push(unique_name);
+            names
+        })
+    }
+
+    /// The scene to inherit from as a `res://` path and the node type of its
+    /// root node
+    fn get_base_scene(root: &Path, scene: &str) -> Result<(String, FtwNodeType), FtwError> {
+        let scene_path = util::get_scene_path(scene);
+        let tscn_file = Path::new("godot").join(&scene_path);
+        let ftw_scene = FtwScene::load(&root.join(&tscn_file))?;
+        let root_node = ftw_scene.get_root().ok_or_else(|| {
+            FtwError::InvalidScene(tscn_file.display().to_string().replace('\\', "/"))
+        })?;
+        let node_type = ftw_scene.get_node_type(root, &root_node)?;
+        let node_type = FtwCommand::parse_node_type(&node_type).ok_or_else(|| {
+            FtwError::InvalidRootNodeType(
+                tscn_file.display().to_string().replace('\\', "/"),
+                node_type,
+            )
+        })?;
+        Ok((format!("res://{scene_path}"), node_type))
+    }
+
+    fn get_dir_path(directories: &[String]) -> String {
+        if directories.is_empty() {
+            String::new()
+        } else {
+            let mut dir = directories.join("/");
+            dir.push('/');
+            dir
+        }
+    }
+
+    fn get_class_tmpl_globals(
+        class_name: &str,
+        node_type: FtwNodeType,
+        properties: &[FtwProperty],
+        signals: &[FtwSignal],
+    ) -> Object {
+        let mut tmpl_globals = FtwCommand::get_tmpl_globals(class_name, node_type);
+        let k = KStringBase::from_ref("properties");
+        let v = Value::Array(
+            properties
+                .iter()
+                .map(|property| Value::Object(property.to_tmpl_object()))
+                .collect(),
+        );
+        tmpl_globals.insert(k, v);
+        let k = KStringBase::from_ref("signals");
+        let v = Value::Array(
+            signals
+                .iter()
+                .map(|signal| Value::Object(signal.to_tmpl_object()))
+                .collect(),
+        );
+        tmpl_globals.insert(k, v);
+        tmpl_globals
+    }
+
+    fn create_resource_rs_file(
+        root: &Path,
+        class_name: &str,
+        directories: &[String],
+        properties: &[FtwProperty],
+        listener: &dyn FtwListener,
+    ) -> Result<(), FtwError> {
+        let base_src_path = "rust/src";
+        let src_dir_path = FtwCommand::create_directory(root, base_src_path, directories)?;
+        let class_name_snake_case = class_name._snake_case();
+        let resource_rs_file = format!("{src_dir_path}/{class_name_snake_case}.rs");
+        if !root.join(&resource_rs_file).exists() {
+            let tmpl_globals = FtwCommand::get_class_tmpl_globals(
+                class_name,
+                FtwNodeType::Resource,
+                properties,
+                &[],
+            );
+            let template = &String::from_utf8_lossy(include_bytes!("templates/resource_tmpl.rs"));
+            FtwCommand::create_file(root, template, &resource_rs_file, &tmpl_globals, listener)?;
         }
+        FtwCommand::create_mod_rs_file(root, base_src_path, directories, listener)?;
         Ok(())
     }
 
-    fn clean() -> Result<(), FtwError> {
+    fn create_tres_file(
+        root: &Path,
+        class_name: &str,
+        directories: &[String],
+        properties: &[FtwProperty],
+        listener: &dyn FtwListener,
+    ) -> Result<(), FtwError> {
+        let tres_dir_path = FtwCommand::create_directory(root, "godot/resources", directories)?;
+        let class_name_pascal_case = class_name._pascal_case();
+        let tres_file = format!("{tres_dir_path}/{class_name_pascal_case}.tres");
+        if !root.join(&tres_file).exists() {
+            let mut tmpl_globals = FtwCommand::get_class_tmpl_globals(
+                class_name,
+                FtwNodeType::Resource,
+                properties,
+                &[],
+            );
+            let k = KStringBase::from_ref("dir_path");
+            let v = Value::Scalar(ScalarCow::from(FtwCommand::get_dir_path(directories)));
+            tmpl_globals.insert(k, v);
+            let template = &String::from_utf8_lossy(include_bytes!("templates/tres_tmpl.tres"));
+            FtwCommand::create_file(root, template, &tres_file, &tmpl_globals, listener)?;
+        }
+        Ok(())
+    }
+
+    /// Creates the class and `.gdns` of an existing scene with the type of its
+    /// root node then attaches the class to the root node, returns the class
+    /// name, the node type and the path of the scene
+    fn attach_class(
+        root: &Path,
+        scene: &str,
+        listener: &dyn FtwListener,
+    ) -> Result<(ClassName, FtwNodeType, String), FtwError> {
+        let scene_path = util::get_scene_path(scene);
+        let tscn_file = Path::new("godot").join(&scene_path);
+        let tscn_file_display = tscn_file.display().to_string().replace('\\', "/");
+        let mut ftw_scene = FtwScene::load(&root.join(&tscn_file))?;
+        let root_node = ftw_scene
+            .get_root()
+            .ok_or_else(|| FtwError::InvalidScene(tscn_file_display.clone()))?;
+        let node_type = ftw_scene.get_node_type(root, &root_node)?;
+        let node_type = FtwCommand::parse_node_type(&node_type)
+            .ok_or_else(|| FtwError::InvalidRootNodeType(tscn_file_display.clone(), node_type))?;
+        let scene_path = Path::new(&scene_path);
+        let class_name = scene_path
+            .file_stem()
+            .and_then(|file_stem| file_stem.to_str())
+            .ok_or(FtwError::PathError)?
+            ._pascal_case();
+        let scene_dir = scene_path.parent().ok_or(FtwError::PathError)?;
+        let directories: Vec<String> = scene_dir
+            .strip_prefix("scenes")
+            .unwrap_or(scene_dir)
+            .iter()
+            .filter_map(|directory| directory.to_str().map(String::from))
+            .collect();
+        let dir_path = FtwCommand::get_dir_path(&directories);
+        ftw_scene.attach_script(
+            &format!("res://native/{dir_path}{class_name}.gdns"),
+            &tscn_file_display,
+        )?;
+        FtwCommand::create_class_rs_file(
+            root,
+            &class_name,
+            &directories,
+            node_type,
+            &[],
+            &[],
+            listener,
+        )?;
+        FtwCommand::create_gdns_file(root, &class_name, &directories, node_type, listener)?;
+        ftw_scene.save(&root.join(&tscn_file))?;
+        listener.on_event(&FtwEvent::FileUpdated(tscn_file_display.clone()));
+        FtwCommand::create_lib_rs_file(root, &class_name, node_type, listener)?;
+        Ok((class_name, node_type, tscn_file_display))
+    }
+
+    /// Generates the typed accessors of the child nodes of the scene of a class
+    /// between the bindings markers of its rust module, returns the number of
+    /// accessors
+    fn generate_bindings(
+        root: &Path,
+        class_name: &str,
+        directories: &[String],
+        listener: &dyn FtwListener,
+    ) -> Result<usize, FtwError> {
+        let dir_path = FtwCommand::get_dir_path(directories);
+        let class_name_pascal_case = class_name._pascal_case();
+        let class_name_snake_case = class_name._snake_case();
+        let tscn_file = format!("godot/scenes/{dir_path}{class_name_pascal_case}.tscn");
+        let class_rs_file = format!("rust/src/{dir_path}{class_name_snake_case}.rs");
+        let contents = read_to_string(root.join(&class_rs_file))
+            .map_err(|_| FtwError::MissingClass(class_rs_file.clone()))?;
+        let ftw_scene = FtwScene::load(&root.join(&tscn_file))?;
+        let root_node = ftw_scene
+            .get_root()
+            .ok_or_else(|| FtwError::InvalidScene(tscn_file.clone()))?;
+        let node_type = ftw_scene.get_node_type(root, &root_node)?;
+        let bindings = FtwBinding::get_bindings(root, &ftw_scene)?;
+        let class_path = format!("{dir_path}{class_name_pascal_case}");
+        let mut tmpl_globals =
+            object!({ "scene_path": tscn_file, "class_path": class_path, "node_type": node_type });
+        let k = KStringBase::from_ref("bindings");
+        let v = Value::Array(
+            bindings
+                .iter()
+                .map(|binding| Value::Object(binding.to_tmpl_object()))
+                .collect(),
+        );
+        tmpl_globals.insert(k, v);
+        let builder = ParserBuilder::with_stdlib().build()?;
+        let template = builder.parse(&String::from_utf8_lossy(include_bytes!(
+            "templates/bindings_tmpl.rs"
+        )))?;
+        let output = template.render(&tmpl_globals)?;
+        write(
+            root.join(&class_rs_file),
+            FtwBinding::replace_bindings(&contents, &output),
+        )?;
+        listener.on_event(&FtwEvent::FileUpdated(class_rs_file));
+        Ok(bindings.len())
+    }
+
+    fn clean(
+        root: &Path,
+        ftw_cfg: &FtwConfiguration,
+        session: &Rc<FtwContainerSession>,
+    ) -> Result<(), FtwError> {
         let compiler =
-            FtwConfiguration::new().get_compiler(FtwTarget::default(), FtwBuildType::default());
+            ftw_cfg.get_compiler(root, FtwTarget::default(), FtwBuildType::default(), session);
         compiler.clean()
     }
 
-    fn build_lib(target: FtwTarget, build_type: FtwBuildType) -> Result<(), FtwError> {
-        let compiler = FtwConfiguration::new().get_compiler(target, build_type);
-        compiler.build()
+    pub(crate) fn build_lib(
+        root: &Path,
+        ftw_cfg: &FtwConfiguration,
+        session: &Rc<FtwContainerSession>,
+        target: FtwTarget,
+        build_type: FtwBuildType,
+        listener: &dyn FtwListener,
+    ) -> Result<bool, FtwError> {
+        let compiler = ftw_cfg.get_compiler(root, target, build_type, session);
+        let is_lib_installed = compiler.build()?;
+        FtwCommand::write_manifest(
+            root,
+            FtwManifestKind::Lib,
+            target,
+            build_type,
+            None,
+            listener,
+        )?;
+        FtwCommand::sync_gdnlib(root, listener)?;
+        Ok(is_lib_installed)
+    }
+
+    fn sync_gdnlib(root: &Path, listener: &dyn FtwListener) -> Result<(), FtwError> {
+        let gdnlib_path = root.join(GDNLIB_FILE);
+        if !gdnlib_path.exists() {
+            return Ok(());
+        }
+        let crate_name = util::get_crate_name_from_path(&root.join("rust"))?;
+        let mut gdnlib = FtwGdnlib::load(&gdnlib_path)?;
+        if gdnlib.sync(&crate_name) && gdnlib.save(&gdnlib_path)? {
+            listener.on_event(&FtwEvent::FileUpdated(GDNLIB_FILE.to_string()));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn export_game(
+        root: &Path,
+        ftw_cfg: &FtwConfiguration,
+        session: &Rc<FtwContainerSession>,
+        target: FtwTarget,
+        build_type: FtwBuildType,
+        is_lib_installed: bool,
+        listener: &dyn FtwListener,
+    ) -> Result<bool, FtwError> {
+        let compiler = ftw_cfg.get_compiler(root, target, build_type, session);
+        if !is_lib_installed && compiler.is_exported_game_up_to_date()? {
+            return Ok(false);
+        }
+        let crate_name = util::get_crate_name_from_path(&root.join("rust"))?;
+        let mut gdnlib_switch = FtwGdnlibSwitch::new(&root.join(GDNLIB_FILE), target, &crate_name)?;
+        compiler.export()?;
+        gdnlib_switch.restore()?;
+        let godot_version = compiler.get_godot_version();
+        FtwCommand::write_manifest(
+            root,
+            FtwManifestKind::Game,
+            target,
+            build_type,
+            godot_version,
+            listener,
+        )?;
+        Ok(true)
+    }
+
+    fn package_game(
+        root: &Path,
+        ftw_cfg: &FtwConfiguration,
+        target: FtwTarget,
+        build_type: FtwBuildType,
+    ) -> Result<String, FtwError> {
+        let package = FtwPackage::create(root, target, build_type, &ftw_cfg.package_extra_files)?;
+        let package_path = package.write(root)?;
+        Ok(package_path.display().to_string())
+    }
+
+    fn sync_presets(
+        root: &Path,
+        listener: &dyn FtwListener,
+    ) -> Result<(Vec<String>, usize), FtwError> {
+        let crate_name = util::get_crate_name_from_path(&root.join("rust"))?;
+        let presets_path = root.join(EXPORT_PRESETS_FILE);
+        let mut presets = FtwPresets::load(&presets_path)?;
+        let (added, drifts) = presets.sync(&crate_name);
+        for name in &added {
+            listener.on_event(&FtwEvent::PresetAdded(name.clone()));
+        }
+        for drift in &drifts {
+            listener.on_event(&FtwEvent::PresetDrifted(drift.clone()));
+        }
+        presets.save(&presets_path)?;
+        Ok((added, drifts.len()))
+    }
+
+    fn create_keystore(
+        root: &Path,
+        build_type: FtwBuildType,
+        listener: &dyn FtwListener,
+    ) -> Result<(String, usize), FtwError> {
+        let crate_name = util::get_crate_name_from_path(&root.join("rust"))?;
+        let keystore = FtwKeystore::new(build_type, &crate_name);
+        let keystore_path = keystore.get_path();
+        if keystore.create(root)? {
+            listener.on_event(&FtwEvent::FileCreated(keystore_path.clone()));
+        }
+        let presets_path = root.join(EXPORT_PRESETS_FILE);
+        let mut presets = FtwPresets::load(&presets_path)?;
+        presets.sync(&crate_name);
+        let names = keystore.configure(&mut presets)?;
+        presets.save(&presets_path)?;
+        Ok((keystore_path, names.len()))
     }
 
-    fn export_game(target: FtwTarget, build_type: FtwBuildType) -> Result<(), FtwError> {
-        let compiler = FtwConfiguration::new().get_compiler(target, build_type);
-        compiler.export()
+    pub(crate) fn check_keystores(
+        root: &Path,
+        targets: &[FtwTarget],
+        build_type: FtwBuildType,
+    ) -> Result<(), FtwError> {
+        if !targets.iter().any(|target| target.is_android()) {
+            return Ok(());
+        }
+        let presets = FtwPresets::load(&root.join(EXPORT_PRESETS_FILE))?;
+        for target in targets.iter().filter(|target| target.is_android()) {
+            FtwKeystore::check(root, *target, build_type, &presets)?;
+        }
+        Ok(())
     }
 
-    fn run_with_godot(machine_type: &FtwMachineType) -> Result<(), FtwError> {
-        let godot_executable = util::get_godot_exe_for_running(machine_type);
-        cmd!((godot_executable) ("--path") ("godot/") if (machine_type.is_desktop()) { (machine_type.to_cli_arg()) }).run()
+    fn write_manifest(
+        root: &Path,
+        kind: FtwManifestKind,
+        target: FtwTarget,
+        build_type: FtwBuildType,
+        godot_version: Option<String>,
+        listener: &dyn FtwListener,
+    ) -> Result<(), FtwError> {
+        let manifest = FtwManifest::create(root, kind, target, build_type, godot_version)?;
+        let manifest_path = manifest.save(root)?;
+        let manifest_path = manifest_path.display().to_string();
+        listener.on_event(&FtwEvent::FileCreated(manifest_path));
+        Ok(())
+    }
+
+    /// Reports the template version and the missing required paths of the
+    /// project to the listener, the missing target directories are recreated
+    /// if `fix` is set
+    fn check_project(
+        root: &Path,
+        fix: bool,
+        listener: &dyn FtwListener,
+    ) -> Result<(Option<String>, usize), FtwError> {
+        let mut check = FtwCheck::new(root);
+        listener.on_event(&FtwEvent::TemplateVersion(check.tag.clone()));
+        let fixed = if fix { check.fix(root)? } else { vec![] };
+        for path in &fixed {
+            listener.on_event(&FtwEvent::PathFixed(path.clone()));
+        }
+        for path in &check.missing {
+            listener.on_event(&FtwEvent::PathMissing(path.clone()));
+        }
+        if check.is_valid() {
+            Ok((check.tag, fixed.len()))
+        } else {
+            Err(FtwError::InvalidProject(check.missing))
+        }
+    }
+
+    /// Renders `template` at `tag` inside `destination` the same way `ftw new`
+    /// does, returns the directory of the rendered project
+    fn render_template(
+        project_name: &str,
+        template: &FtwTemplate,
+        tag: &FtwTag,
+        destination: &Path,
+    ) -> Result<PathBuf, FtwError> {
+        create_dir_all(destination)?;
+        let project_dir = FtwCommand::generate_project(
+            project_name,
+            template,
+            tag,
+            Some(destination.to_path_buf()),
+            Vcs::None,
+        )?;
+        let project_dir_display = project_dir.display().to_string();
+        FtwCommand::append_to_gitignore(&project_dir_display)?;
+        FtwCommand::delete_items(&project_dir_display)?;
+        Ok(project_dir)
+    }
+
+    /// Merges the differences between the `old_tag` and `new_tag` renders of
+    /// `template` into the project and reports every changed file to the
+    /// listener, `.tag` is updated unless there are conflicts
+    fn upgrade_project(
+        root: &Path,
+        template: &FtwTemplate,
+        old_tag: &FtwTag,
+        new_tag: &FtwTag,
+        listener: &dyn FtwListener,
+    ) -> Result<usize, FtwError> {
+        let crate_name = util::get_crate_name_from_path(&root.join("rust"))?;
+        let upgrade_dir = env::temp_dir().join(format!("ftw-upgrade-{}", nanoid!()));
+        let render_and_merge = || {
+            let old_dir = FtwCommand::render_template(
+                &crate_name,
+                template,
+                old_tag,
+                &upgrade_dir.join("old"),
+            )?;
+            let new_dir = FtwCommand::render_template(
+                &crate_name,
+                template,
+                new_tag,
+                &upgrade_dir.join("new"),
+            )?;
+            FtwUpgrade::merge(&old_dir, &new_dir, root)
+        };
+        let upgrade = render_and_merge();
+        drop(remove_items(&[&upgrade_dir]));
+        let upgrade = upgrade?;
+        for FtwUpgradeChange { path, status } in &upgrade.changes {
+            listener.on_event(&FtwEvent::FileUpgraded {
+                path: path.clone(),
+                status: *status,
+            });
+        }
+        let conflicts = upgrade.get_conflicts();
+        if conflicts.is_empty() {
+            write(root.join(TAG_FILE), new_tag.to_git_tag())?;
+            Ok(upgrade.changes.len())
+        } else {
+            Err(FtwError::UpgradeConflicts(new_tag.to_git_tag(), conflicts))
+        }
+    }
+
+    fn verify_artifacts(root: &Path, listener: &dyn FtwListener) -> Result<usize, FtwError> {
+        let manifests = FtwManifest::load_all(root)?;
+        let mut is_valid = true;
+        for manifest in &manifests {
+            listener.on_event(&FtwEvent::ManifestLoaded(manifest.clone()));
+            for (artifact, status) in manifest.verify(root) {
+                listener.on_event(&FtwEvent::ArtifactVerified {
+                    artifact: artifact.clone(),
+                    status,
+                });
+                is_valid = is_valid && status == FtwArtifactStatus::Ok;
+            }
+        }
+        if is_valid {
+            Ok(manifests.len())
+        } else {
+            Err(FtwError::InvalidArtifacts)
+        }
+    }
+
+    /// Exports the game for `target` (unless it is up to date) and runs it,
+    /// returns `true` if the exported game was up to date
+    fn run_exported_game(
+        root: &Path,
+        ftw_cfg: &FtwConfiguration,
+        session: &Rc<FtwContainerSession>,
+        target: FtwTarget,
+        build_type: FtwBuildType,
+        godot_args: &[String],
+        listener: &dyn FtwListener,
+    ) -> Result<bool, FtwError> {
+        let current_platform: FtwTarget = util::get_current_platform().parse().unwrap_or_default();
+        let is_runnable = !target.is_android()
+            && !target.is_ios()
+            && target.to_export_name() == current_platform.to_export_name();
+        if !is_runnable {
+            return Err(FtwError::UnsupportedTarget);
+        }
+        let is_lib_installed =
+            FtwCommand::build_lib(root, ftw_cfg, session, target, build_type, listener)?;
+        let is_exported = FtwCommand::export_game(
+            root,
+            ftw_cfg,
+            session,
+            target,
+            build_type,
+            is_lib_installed,
+            listener,
+        )?;
+        session.stop();
+        let compiler = ftw_cfg.get_compiler(root, target, build_type, session);
+        let export_path = root.join(compiler.get_export_path()?).canonicalize()?;
+        if target.is_macos() {
+            let app_dir = export_path.with_extension("");
+            let mut archive = ZipArchive::new(File::open(&export_path)?)?;
+            archive.extract(&app_dir)?;
+            let app = read_dir(&app_dir)?
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .find(|path| path.extension().is_some_and(|ext| ext == "app"))
+                .ok_or(FtwError::PathError)?;
+            cmd!(open ("-W") ("-n") (app) if (!godot_args.is_empty()) { ("--args") [godot_args] })
+                .run()?;
+        } else {
+            Command::new(export_path).args(godot_args).run()?;
+        }
+        Ok(!is_exported)
+    }
+
+    /// The server runs with the executable found by
+    /// [`util::get_godot_exe_for_server`] instead of being tied to a target
+    pub(crate) fn get_godot_command(
+        root: &Path,
+        ftw_cfg: &FtwConfiguration,
+        machine_type: &FtwMachineType,
+        godot_args: &[String],
+    ) -> Result<Command, FtwError> {
+        let (godot_executable, server_args) = match machine_type {
+            FtwMachineType::Desktop => (
+                util::get_godot_exe_for_running(ftw_cfg, machine_type),
+                vec![],
+            ),
+            FtwMachineType::Server => util::get_godot_exe_for_server(ftw_cfg)?,
+        };
+        let godot_path = root.join("godot");
+        Ok(cmd!((godot_executable) ("--path") (godot_path) if (machine_type.is_desktop()) { (machine_type.to_cli_arg()) } [server_args] [godot_args]))
+    }
+
+    fn get_instance_commands(
+        root: &Path,
+        ftw_cfg: &FtwConfiguration,
+        instances: &[FtwInstance],
+        godot_args: &[String],
+    ) -> Result<Vec<(FtwInstance, Command)>, FtwError> {
+        instances
+            .iter()
+            .map(|instance| {
+                let instance_args = instance.get_args(godot_args);
+                let command = FtwCommand::get_godot_command(
+                    root,
+                    ftw_cfg,
+                    &instance.machine_type,
+                    &instance_args,
+                )?;
+                Ok((*instance, command))
+            })
+            .collect()
+    }
+
+    /// Runs `command` with every line of its standard output reported to the
+    /// listener as it comes, returns the captured output and `true` if it
+    /// exited successfully
+    fn run_and_capture(
+        command: &mut Command,
+        listener: &dyn FtwListener,
+    ) -> Result<(String, bool), FtwError> {
+        let mut child = command.stdout(Stdio::piped()).spawn()?;
+        let mut output = String::new();
+        if let Some(stdout) = child.stdout.take() {
+            for line in BufReader::new(stdout).split(b'\n').map_while(Result::ok) {
+                let line = String::from_utf8_lossy(&line);
+                listener.on_event(&FtwEvent::OutputLine(line.to_string()));
+                output.push_str(&line);
+                output.push('\n');
+            }
+        }
+        let status = child.wait()?;
+        Ok((output, status.success()))
+    }
+
+    /// Maps the test runner to a `res://` path, it can be a scene or a script
+    fn get_test_runner_path(root: &Path, runner: &str) -> Result<String, FtwError> {
+        let runner = runner.replace('\\', "/");
+        let runner = runner.strip_prefix("res://").unwrap_or(&runner);
+        let runner = runner.strip_prefix("godot/").unwrap_or(runner);
+        if root.join("godot").join(runner).is_file() {
+            Ok(format!("res://{runner}"))
+        } else {
+            Err(FtwError::MissingTestRunner(runner.to_string()))
+        }
+    }
+
+    fn test_rust(root: &Path, listener: &dyn FtwListener) -> Result<FtwTestSuite, FtwError> {
+        let (output, is_success) =
+            FtwCommand::run_and_capture(cmd!(cargo test).current_dir(root.join("rust")), listener)?;
+        Ok(FtwTestSuite::from_cargo_output(&output, is_success))
+    }
+
+    /// Runs the test runner with godot headless, a script runner is run with
+    /// `-s` and a scene runner as the main scene
+    fn test_godot(
+        root: &Path,
+        ftw_cfg: &FtwConfiguration,
+        runner: &str,
+        runner_args: &[String],
+        listener: &dyn FtwListener,
+    ) -> Result<FtwTestSuite, FtwError> {
+        let (godot_executable, godot_args) = util::get_godot_exe_for_testing(ftw_cfg);
+        let is_script = Path::new(runner)
+            .extension()
+            .is_some_and(|ext| ext == "gd" || ext == "gdns");
+        let godot_path = root.join("godot");
+        let (output, is_success) = FtwCommand::run_and_capture(&mut cmd!((godot_executable) [godot_args] ("--path") (godot_path) if (is_script) { ("-s") } (runner) [runner_args]), listener)?;
+        Ok(FtwTestSuite::from_godot_output(&output, is_success))
+    }
+
+    /// Builds the library and reports a compact pass/fail event, returns
+    /// `true` if the build passed
+    fn rebuild_lib(
+        root: &Path,
+        ftw_cfg: &FtwConfiguration,
+        session: &Rc<FtwContainerSession>,
+        target: FtwTarget,
+        build_type: FtwBuildType,
+        listener: &dyn FtwListener,
+    ) -> bool {
+        let started = Instant::now();
+        let result = FtwCommand::build_lib(root, ftw_cfg, session, target, build_type, listener)
+            .map(|is_lib_installed| !is_lib_installed)
+            .map_err(|error| error.to_string());
+        let is_passing = result.is_ok();
+        listener.on_event(&FtwEvent::LibraryRebuilt {
+            target,
+            build_type,
+            result,
+            elapsed: started.elapsed(),
+        });
+        is_passing
+    }
+
+    /// Starts the game of `ftw watch --run`, it is killed on Ctrl-C
+    fn start_game(command: &mut Command) -> Result<(), FtwError> {
+        CTRL_C_HANDLER.call_once(|| util::on_ctrl_c(FtwCommand::kill_game));
+        let child = command.spawn()?;
+        if let Ok(mut game) = WATCHED_GAME.lock() {
+            *game = Some(child);
+        }
+        Ok(())
+    }
+
+    /// Stops the game of `ftw watch --run`, returns `true` if it was started
+    fn stop_game() -> bool {
+        let game = WATCHED_GAME.lock().ok().and_then(|mut game| game.take());
+        match game {
+            Some(mut child) => {
+                drop(child.kill());
+                drop(child.wait());
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn kill_game() {
+        FtwCommand::stop_game();
+    }
+
+    /// Rebuilds the library whenever the rust sources or the `Cargo.toml`
+    /// files change, the game is stopped while building and started again
+    /// after every passing build if `run` is set, so after a failing build it
+    /// stays stopped until the next passing one
+    fn watch_lib(
+        root: &Path,
+        ftw_cfg: &FtwConfiguration,
+        session: &Rc<FtwContainerSession>,
+        target: FtwTarget,
+        build_type: FtwBuildType,
+        run: bool,
+        listener: &dyn FtwListener,
+    ) -> Result<Infallible, FtwError> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(250);
+        const DEBOUNCE: Duration = Duration::from_millis(500);
+        let machine_type = FtwMachineType::Desktop;
+        let mut watcher = FtwWatcher::new(FtwWatcher::get_project_paths(root))?;
+        let watched_paths: Vec<String> = watcher
+            .paths
+            .iter()
+            .map(|path| {
+                path.strip_prefix(root)
+                    .unwrap_or(path)
+                    .display()
+                    .to_string()
+            })
+            .collect();
+        listener.on_event(&FtwEvent::WatchStarted(watched_paths));
+        let mut is_passing =
+            FtwCommand::rebuild_lib(root, ftw_cfg, session, target, build_type, listener);
+        loop {
+            if run && is_passing {
+                let mut godot_command =
+                    FtwCommand::get_godot_command(root, ftw_cfg, &machine_type, &[])?;
+                FtwCommand::start_game(&mut godot_command)?;
+                listener.on_event(&FtwEvent::GameStarted { machine_type });
+            }
+            let changes = watcher.wait_for_changes(POLL_INTERVAL, DEBOUNCE)?;
+            listener.on_event(&FtwEvent::FilesChanged(changes.len()));
+            if FtwCommand::stop_game() {
+                listener.on_event(&FtwEvent::GameStopped);
+            }
+            is_passing =
+                FtwCommand::rebuild_lib(root, ftw_cfg, session, target, build_type, listener);
+        }
     }
 }
 
 #[rustfmt::skip]
 impl Processor for FtwCommand {
-    fn process(&self) -> FtwResult {
+    fn process(&self) -> FtwResult<'_> {
+        let root = Path::new(".");
+        let listener: &dyn FtwListener = &FtwPrinter;
         match self {
             FtwCommand::New { project_name, template, tag } => {
-                FtwCommand::generate_project(project_name, template, tag)?;
+                FtwCommand::generate_project(project_name, template, tag, None, Vcs::Git)?;
                 FtwCommand::append_to_gitignore(project_name)?;
                 FtwCommand::delete_items(project_name)?;
                 let project_name = project_name.to_string();
                 Ok(FtwSuccess::New { project_name, template, tag })
             }
-            FtwCommand::Class { class_name, node_type } => {
-                FtwCommand::is_valid_project()?;
+            FtwCommand::Class { class_name, node_type, properties, signals, extends_scene, children } => {
+                FtwCommand::is_valid_project(root, listener)?;
+                let properties: Vec<FtwProperty> = properties.iter().map(|property| property.parse()).collect::<Result<_, _>>()?;
+                let signals: Vec<FtwSignal> = signals.iter().map(|signal| signal.parse()).collect::<Result<_, _>>()?;
+                let children: Vec<FtwNodeType> = children.iter().map(|child| FtwCommand::parse_node_type(child).ok_or_else(|| FtwError::InvalidChild(child.to_string()))).collect::<Result<_, _>>()?;
+                let (base_scene, node_type) = match extends_scene {
+                    Some(scene) => FtwCommand::get_base_scene(root, scene).map(|(base_scene, node_type)| (Some(base_scene), node_type))?,
+                    None => (None, *node_type),
+                };
                 let (class_name, directories) = util::get_class_name_and_directories(class_name);
-                FtwCommand::create_class_rs_file(&class_name, &directories, *node_type)?;
-                FtwCommand::create_gdns_file(&class_name, &directories, *node_type)?;
-                FtwCommand::create_tscn_file(&class_name, &directories, *node_type)?;
-                FtwCommand::create_lib_rs_file(&class_name, *node_type)?;
+                FtwCommand::create_class_rs_file(root, &class_name, &directories, node_type, &properties, &signals, listener)?;
+                FtwCommand::create_gdns_file(root, &class_name, &directories, node_type, listener)?;
+                FtwCommand::create_tscn_file(root, &class_name, &directories, node_type, &properties, base_scene.as_deref(), &children, listener)?;
+                FtwCommand::create_lib_rs_file(root, &class_name, node_type, listener)?;
                 Ok(FtwSuccess::Class { class_name, node_type })
             }
             FtwCommand::Singleton { class_name } => {
-                FtwCommand::is_valid_project()?;
+                FtwCommand::is_valid_project(root, listener)?;
                 let node_type = FtwNodeType::default();
                 let (class_name, directories) = util::get_class_name_and_directories(class_name);
-                FtwCommand::create_class_rs_file(&class_name, &directories, node_type)?;
-                FtwCommand::create_gdns_file(&class_name, &directories, node_type)?;
-                FtwCommand::create_lib_rs_file(&class_name, node_type)?;
+                FtwCommand::create_class_rs_file(root, &class_name, &directories, node_type, &[], &[], listener)?;
+                FtwCommand::create_gdns_file(root, &class_name, &directories, node_type, listener)?;
+                FtwCommand::create_lib_rs_file(root, &class_name, node_type, listener)?;
                 println!("Open Project -> Project Settings -> Autoload and then add the newly created *.gdns file as an autoload");
                 // TODO: parse and modify project.godot file to include the newly created *.gdns file as an autoload
                 Ok(FtwSuccess::Singleton { class_name })
             }
-            FtwCommand::Run { machine_type } => {
-                FtwCommand::is_valid_project()?;
-                let build_type = FtwBuildType::default();
+            FtwCommand::Attach { scene } => {
+                FtwCommand::is_valid_project(root, listener)?;
+                let (class_name, node_type, scene) = FtwCommand::attach_class(root, scene, listener)?;
+                Ok(FtwSuccess::Attach { class_name, node_type, scene })
+            }
+            FtwCommand::Bindings { class_name } => {
+                FtwCommand::is_valid_project(root, listener)?;
+                let (class_name, directories) = util::get_class_name_and_directories(class_name);
+                let nodes = FtwCommand::generate_bindings(root, &class_name, &directories, listener)?;
+                Ok(FtwSuccess::Bindings { class_name, nodes })
+            }
+            FtwCommand::Resource { class_name, properties } => {
+                FtwCommand::is_valid_project(root, listener)?;
+                let node_type = FtwNodeType::Resource;
+                let properties: Vec<FtwProperty> = properties.iter().map(|property| property.parse()).collect::<Result<_, _>>()?;
+                let (class_name, directories) = util::get_class_name_and_directories(class_name);
+                FtwCommand::create_resource_rs_file(root, &class_name, &directories, &properties, listener)?;
+                FtwCommand::create_gdns_file(root, &class_name, &directories, node_type, listener)?;
+                FtwCommand::create_tres_file(root, &class_name, &directories, &properties, listener)?;
+                FtwCommand::create_lib_rs_file(root, &class_name, node_type, listener)?;
+                Ok(FtwSuccess::Resource { class_name, properties: properties.len() })
+            }
+            FtwCommand::Run { machine_type: _, build_type, exported: Some(target), options, instances: _ } => {
+                FtwCommand::is_valid_project(root, listener)?;
+                let godot_args = options.to_godot_args(root)?;
+                let ftw_cfg = FtwConfiguration::from_root(root);
+                let session = ftw_cfg.get_container_session(root);
+                let is_up_to_date = FtwCommand::run_exported_game(root, &ftw_cfg, &session, *target, *build_type, &godot_args, listener)?;
+                Ok(FtwSuccess::RunExported { target, build_type, is_up_to_date })
+            }
+            FtwCommand::Run { machine_type: _, build_type, exported: None, options, instances } if !instances.is_empty() => {
+                FtwCommand::is_valid_project(root, listener)?;
+                let godot_args = options.to_godot_args(root)?;
                 let current_platform = util::get_current_platform();
                 let target: FtwTarget = current_platform.parse().unwrap_or_default();
-                if machine_type.is_server() {
-                    target.is_linux_server()?;
-                }
-                FtwCommand::build_lib(target, build_type)?;
-                FtwCommand::run_with_godot(machine_type)?;
-                Ok(FtwSuccess::Run { machine_type })
+                let ftw_cfg = FtwConfiguration::from_root(root);
+                let instance_commands = FtwCommand::get_instance_commands(root, &ftw_cfg, instances, &godot_args)?;
+                let session = ftw_cfg.get_container_session(root);
+                let is_lib_installed = FtwCommand::build_lib(root, &ftw_cfg, &session, target, *build_type, listener)?;
+                session.stop();
+                FtwInstance::run_all(instance_commands)?;
+                let instances = instances.iter().map(FtwInstance::get_name).collect();
+                let is_up_to_date = !is_lib_installed;
+                Ok(FtwSuccess::RunInstances { instances, is_up_to_date })
+            }
+            FtwCommand::Run { machine_type, build_type, exported: None, options, instances: _ } => {
+                FtwCommand::is_valid_project(root, listener)?;
+                let godot_args = options.to_godot_args(root)?;
+                let build_type = *build_type;
+                let current_platform = util::get_current_platform();
+                let target: FtwTarget = current_platform.parse().unwrap_or_default();
+                let ftw_cfg = FtwConfiguration::from_root(root);
+                let mut godot_command = FtwCommand::get_godot_command(root, &ftw_cfg, machine_type, &godot_args)?;
+                let session = ftw_cfg.get_container_session(root);
+                let is_lib_installed = FtwCommand::build_lib(root, &ftw_cfg, &session, target, build_type, listener)?;
+                session.stop();
+                godot_command.run()?;
+                let is_up_to_date = !is_lib_installed;
+                Ok(FtwSuccess::Run { machine_type, is_up_to_date })
             }
             FtwCommand::Build { targets, build_type } => {
-                FtwCommand::is_valid_project()?;
+                FtwCommand::is_valid_project(root, listener)?;
+                let ftw_cfg = FtwConfiguration::from_root(root);
+                let session = ftw_cfg.get_container_session(root);
+                let mut up_to_date_targets = vec![];
                 for target in targets {
-                    FtwCommand::build_lib(*target, *build_type)?;
+                    if !FtwCommand::build_lib(root, &ftw_cfg, &session, *target, *build_type, listener)? {
+                        up_to_date_targets.push(*target);
+                    }
                     thread::sleep(time::Duration::from_millis(100));
                 }
-                Ok(FtwSuccess::Build { targets, build_type })
+                Ok(FtwSuccess::Build { targets, build_type, up_to_date_targets })
             }
             FtwCommand::Export { targets, build_type } => {
-                FtwCommand::is_valid_project()?;
+                FtwCommand::is_valid_project(root, listener)?;
+                FtwCommand::check_keystores(root, targets, *build_type)?;
+                let ftw_cfg = FtwConfiguration::from_root(root);
+                let session = ftw_cfg.get_container_session(root);
+                let mut up_to_date_targets = vec![];
                 for target in targets {
-                    FtwCommand::build_lib(*target, *build_type)?;
-                    FtwCommand::export_game(*target, *build_type)?;
+                    let is_lib_installed = FtwCommand::build_lib(root, &ftw_cfg, &session, *target, *build_type, listener)?;
+                    if !FtwCommand::export_game(root, &ftw_cfg, &session, *target, *build_type, is_lib_installed, listener)? {
+                        up_to_date_targets.push(*target);
+                    }
                 }
-                Ok(FtwSuccess::Export { targets, build_type })
+                Ok(FtwSuccess::Export { targets, build_type, up_to_date_targets })
+            }
+            FtwCommand::Package { targets, build_type } => {
+                FtwCommand::is_valid_project(root, listener)?;
+                FtwCommand::check_keystores(root, targets, *build_type)?;
+                let ftw_cfg = FtwConfiguration::from_root(root);
+                let session = ftw_cfg.get_container_session(root);
+                let mut archives = vec![];
+                for target in targets {
+                    let is_lib_installed = FtwCommand::build_lib(root, &ftw_cfg, &session, *target, *build_type, listener)?;
+                    FtwCommand::export_game(root, &ftw_cfg, &session, *target, *build_type, is_lib_installed, listener)?;
+                    archives.push(FtwCommand::package_game(root, &ftw_cfg, *target, *build_type)?);
+                }
+                Ok(FtwSuccess::Package { build_type, archives })
+            }
+            FtwCommand::PresetsSync => {
+                FtwCommand::is_valid_project(root, listener)?;
+                let (added, drifts) = FtwCommand::sync_presets(root, listener)?;
+                Ok(FtwSuccess::PresetsSync { added, drifts })
+            }
+            FtwCommand::AndroidKeystore { build_type } => {
+                FtwCommand::is_valid_project(root, listener)?;
+                let (keystore, presets) = FtwCommand::create_keystore(root, *build_type, listener)?;
+                Ok(FtwSuccess::AndroidKeystore { build_type, keystore, presets })
+            }
+            FtwCommand::Watch { target, build_type, run } => {
+                FtwCommand::is_valid_project(root, listener)?;
+                let current_platform: FtwTarget = util::get_current_platform().parse().unwrap_or_default();
+                if *run && target.to_export_name() != current_platform.to_export_name() {
+                    return Err(FtwError::UnsupportedTarget);
+                }
+                let ftw_cfg = FtwConfiguration::from_root(root);
+                let session = ftw_cfg.get_container_session(root);
+                match FtwCommand::watch_lib(root, &ftw_cfg, &session, *target, *build_type, *run, listener)? {}
+            }
+            FtwCommand::Test { runner, junit } => {
+                FtwCommand::is_valid_project(root, listener)?;
+                let ftw_cfg = FtwConfiguration::from_root(root);
+                let runner = runner.as_ref().unwrap_or(&ftw_cfg.test_runner);
+                let runner = if runner.is_empty() { None } else { Some(FtwCommand::get_test_runner_path(root, runner)?) };
+                let mut report = FtwTestReport::default();
+                report.suites.push(FtwCommand::test_rust(root, listener)?);
+                match runner {
+                    Some(runner) => {
+                        let session = ftw_cfg.get_container_session(root);
+                        let target: FtwTarget = util::get_current_platform().parse().unwrap_or_default();
+                        FtwCommand::build_lib(root, &ftw_cfg, &session, target, FtwBuildType::Debug, listener)?;
+                        session.stop();
+                        report.suites.push(FtwCommand::test_godot(root, &ftw_cfg, &runner, &ftw_cfg.test_runner_args, listener)?);
+                    }
+                    None => listener.on_event(&FtwEvent::TestRunnerSkipped),
+                }
+                listener.on_event(&FtwEvent::TestReported(report.clone()));
+                if let Some(junit) = junit {
+                    write(junit, report.to_junit_xml())?;
+                    listener.on_event(&FtwEvent::FileCreated(junit.to_string()));
+                }
+                if report.is_passing() {
+                    let passed = report.count(FtwTestStatus::Passed);
+                    let ignored = report.count(FtwTestStatus::Ignored);
+                    Ok(FtwSuccess::Test { passed, ignored })
+                } else {
+                    Err(FtwError::TestsFailed(report.count(FtwTestStatus::Failed)))
+                }
+            }
+            FtwCommand::Upgrade { template, tag } => {
+                FtwCommand::is_valid_project(root, listener)?;
+                let git_tag = FtwCheck::new(root).tag.unwrap_or_else(|| UNTAGGED_TEMPLATE_TAG.to_string());
+                let old_tag = FtwTag::Tagged { git_tag };
+                let changes = if old_tag.to_git_tag() == tag.to_git_tag() {
+                    0
+                } else {
+                    FtwCommand::upgrade_project(root, template, &old_tag, tag, listener)?
+                };
+                Ok(FtwSuccess::Upgrade { tag, changes })
+            }
+            FtwCommand::Check { fix } => {
+                let (tag, fixed) = FtwCommand::check_project(root, *fix, listener)?;
+                Ok(FtwSuccess::Check { tag, fixed })
+            }
+            FtwCommand::Artifacts => {
+                FtwCommand::is_valid_project(root, listener)?;
+                let manifests = FtwCommand::verify_artifacts(root, listener)?;
+                Ok(FtwSuccess::Artifacts { manifests })
             }
             FtwCommand::Clean => {
-                FtwCommand::clean()?;
+                let ftw_cfg = FtwConfiguration::from_root(root);
+                let session = ftw_cfg.get_container_session(root);
+                FtwCommand::clean(root, &ftw_cfg, &session)?;
                 Ok(FtwSuccess::Clean)
             }
         }
@@ -444,7 +1514,7 @@ impl Processor for FtwCommand {
 mod ftw_command_tests {
     use super::*;
     use crate::{
-        test_util::Project,
+        test_util::{create_project_skeleton, Project},
         traits::{ToAppExt, ToLibExt, ToLibPrefix},
     };
     use assert_cmd::prelude::*;
@@ -462,15 +1532,156 @@ mod ftw_command_tests {
         let _ = cmd.process();
         let _ = env::set_current_dir(Path::new(&project.get_name()));
         let _ = remove_items(&["Cargo.toml"]);
-        let res = FtwCommand::is_valid_project();
+        let res = FtwCommand::is_valid_project(Path::new("."), &FtwPrinter);
         match res {
-            Err(FtwError::InvalidProject) => assert!(true),
+            Err(FtwError::InvalidProject(missing)) => assert_eq!(vec!["Cargo.toml"], missing),
             _ => unreachable!(),
         }
         let _ = env::set_current_dir(Path::new("../"));
         drop(project)
     }
 
+    #[test]
+    fn test_find_project_root() -> Result<(), FtwError> {
+        let root = env::temp_dir().join(format!("ftw-project-root-{}", std::process::id()));
+        let enemies = root.join("rust/src/enemies");
+        create_dir_all(&enemies)?;
+        assert_eq!(None, FtwCommand::find_project_root(&enemies));
+        write(root.join("rust/src").join(TAG_FILE), "v1.0.0")?;
+        assert_eq!(None, FtwCommand::find_project_root(&enemies));
+        create_project_skeleton(&root)?;
+        assert_eq!(Some(root.clone()), FtwCommand::find_project_root(&enemies));
+        assert_eq!(Some(root.clone()), FtwCommand::find_project_root(&root));
+        remove_items(&[&root])?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_class_directories() {
+        let directories = vec!["enemies".to_string(), "bosses".to_string()];
+        let cmd = FtwCommand::Class {
+            class_name: "Goblin".to_string(),
+            node_type: FtwNodeType::Area2D,
+            properties: vec![],
+            signals: vec![],
+            extends_scene: None,
+            children: vec![],
+        };
+        assert_eq!(
+            FtwCommand::Class {
+                class_name: "enemies/bosses/Goblin".to_string(),
+                node_type: FtwNodeType::Area2D,
+                properties: vec![],
+                signals: vec![],
+                extends_scene: None,
+                children: vec![],
+            },
+            cmd.with_class_directories(&directories)
+        );
+        let cmd = FtwCommand::Singleton {
+            class_name: "Network".to_string(),
+        };
+        assert_eq!(
+            FtwCommand::Singleton {
+                class_name: "Network".to_string(),
+            },
+            cmd.with_class_directories(&[])
+        );
+        let cmd = FtwCommand::Resource {
+            class_name: "ItemData".to_string(),
+            properties: vec![],
+        };
+        assert_eq!(
+            FtwCommand::Resource {
+                class_name: "enemies/bosses/ItemData".to_string(),
+                properties: vec![],
+            },
+            cmd.with_class_directories(&directories)
+        );
+        let cmd = FtwCommand::Bindings {
+            class_name: "Goblin".to_string(),
+        };
+        assert_eq!(
+            FtwCommand::Bindings {
+                class_name: "enemies/bosses/Goblin".to_string(),
+            },
+            cmd.with_class_directories(&directories)
+        );
+        assert_eq!(
+            FtwCommand::Clean,
+            FtwCommand::Clean.with_class_directories(&directories)
+        );
+    }
+
+    #[test]
+    fn test_with_paths_relative_to() {
+        let root = Path::new("/home/user/game");
+        let current_dir = root.join("godot/native");
+        let attach = |scene: &str| FtwCommand::Attach {
+            scene: scene.to_string(),
+        };
+        let class = |extends_scene: &str| FtwCommand::Class {
+            class_name: "Goblin".to_string(),
+            node_type: FtwNodeType::Node,
+            properties: vec![],
+            signals: vec![],
+            extends_scene: Some(extends_scene.to_string()),
+            children: vec![],
+        };
+        let test = |junit: &str| FtwCommand::Test {
+            runner: None,
+            junit: Some(junit.to_string()),
+        };
+        assert_eq!(
+            attach("godot/scenes/Hero.tscn"),
+            attach("../scenes/Hero.tscn").with_paths_relative_to(&current_dir, root)
+        );
+        assert_eq!(
+            attach("res://scenes/Hero.tscn"),
+            attach("res://scenes/Hero.tscn").with_paths_relative_to(&current_dir, root)
+        );
+        assert_eq!(
+            attach("Hero"),
+            attach("Hero").with_paths_relative_to(&current_dir, root)
+        );
+        assert_eq!(
+            class("godot/scenes/Enemy.tscn"),
+            class("../scenes/Enemy.tscn").with_paths_relative_to(&current_dir, root)
+        );
+        assert_eq!(
+            class("enemies/Enemy"),
+            class("enemies/Enemy").with_paths_relative_to(&current_dir, root)
+        );
+        assert_eq!(
+            test("/home/user/report.xml"),
+            test("../../../report.xml").with_paths_relative_to(&current_dir, root)
+        );
+    }
+
+    #[test]
+    fn test_get_child_names() {
+        let children = [
+            FtwNodeType::Sprite,
+            FtwNodeType::CollisionShape2D,
+            FtwNodeType::Sprite,
+            FtwNodeType::Sprite,
+        ];
+        assert_eq!(
+            vec!["Sprite", "CollisionShape2D", "Sprite2", "Sprite3"],
+            FtwCommand::get_child_names(&children)
+        );
+    }
+
+    #[test]
+    fn test_parse_node_type() {
+        assert_eq!(
+            Some(FtwNodeType::Area2D),
+            FtwCommand::parse_node_type("Area2D")
+        );
+        assert_eq!(Some(FtwNodeType::Node), FtwCommand::parse_node_type("Node"));
+        assert_eq!(None, FtwCommand::parse_node_type("Area3D"));
+    }
+
     #[test]
     fn test_process_ftw_command_new() {
         let project = Project::new();
@@ -690,6 +1901,10 @@ mod ftw_command_tests {
         let cmd = FtwCommand::Class {
             class_name: "MyPlayer".to_string(),
             node_type: FtwNodeType::Area2D,
+            properties: vec![],
+            signals: vec![],
+            extends_scene: None,
+            children: vec![],
         };
         let _ = cmd.process();
         let _ = env::set_current_dir(Path::new("../"));
@@ -734,6 +1949,10 @@ mod ftw_command_tests {
         let cmd = FtwCommand::Class {
             class_name: "MyButtonTool".to_string(),
             node_type: FtwNodeType::Button,
+            properties: vec![],
+            signals: vec![],
+            extends_scene: None,
+            children: vec![],
         };
         let _ = cmd.process();
         let _ = env::set_current_dir(Path::new("../"));
@@ -780,6 +1999,10 @@ mod ftw_command_tests {
         let cmd = FtwCommand::Class {
             class_name: "foo/bar/baz/MyPlayer".to_string(),
             node_type: FtwNodeType::Area2D,
+            properties: vec![],
+            signals: vec![],
+            extends_scene: None,
+            children: vec![],
         };
         let _ = cmd.process();
         let _ = env::set_current_dir(Path::new("../"));
@@ -859,6 +2082,47 @@ mod ftw_command_tests {
             .contains("handle.add_class::<my_player::MyPlayer>();"));
     }
 
+    #[test]
+    fn test_process_ftw_command_resource() {
+        let project = Project::new();
+        let cmd = FtwCommand::New {
+            project_name: project.get_name(),
+            template: FtwTemplate::default(),
+            tag: FtwTag::default(),
+        };
+        let _ = cmd.process();
+        let _ = env::set_current_dir(Path::new(&project.get_name()));
+        let cmd = FtwCommand::Resource {
+            class_name: "items/ItemData".to_string(),
+            properties: vec![
+                "damage:i32".parse().unwrap(),
+                "icon:Texture".parse().unwrap(),
+            ],
+        };
+        let _ = cmd.process();
+        let _ = env::set_current_dir(Path::new("../"));
+        assert!(project.exists("rust/src/items/item_data.rs"));
+        assert!(project.exists("godot/native/items/ItemData.gdns"));
+        assert!(project.exists("godot/resources/items/ItemData.tres"));
+        assert!(project
+            .read("rust/src/items/item_data.rs")
+            .contains("#[inherit(Resource)]"));
+        assert!(project
+            .read("rust/src/items/item_data.rs")
+            .contains("icon: Option<Ref<Texture>>,"));
+        assert!(project
+            .read("godot/resources/items/ItemData.tres")
+            .contains(
+                "[ext_resource path=\"res://native/items/ItemData.gdns\" type=\"Script\" id=1]"
+            ));
+        assert!(project
+            .read("godot/resources/items/ItemData.tres")
+            .contains("damage = 0"));
+        assert!(project
+            .read("rust/src/lib.rs")
+            .contains("handle.add_class::<items::item_data::ItemData>();"));
+    }
+
     #[test]
     fn test_process_ftw_command_build() {
         let project = Project::new();
@@ -901,7 +2165,7 @@ mod ftw_command_tests {
         let contents = r#"[ftw]
 enable-cross-compilation=true
 "#;
-        let _ = project.create(".ftw", contents);
+        project.create(".ftw", contents);
         assert!(project
             .read(".ftw")
             .contains("enable-cross-compilation=true"));
@@ -1057,7 +2321,7 @@ enable-cross-compilation=true
         let contents = r#"[ftw]
 enable-cross-compilation=true
 "#;
-        let _ = project.create(".ftw", contents);
+        project.create(".ftw", contents);
         assert!(project
             .read(".ftw")
             .contains("enable-cross-compilation=true"));
@@ -1067,17 +2331,10 @@ enable-cross-compilation=true
             .arg("switch-gdnlib-msvc-to-gnu-entry")
             .assert()
             .success();
-        Command::new("cargo")
-            .arg("make")
-            .arg("create-debug-keystore")
-            .assert()
-            .success();
-        Command::new("cargo")
-            .arg("make")
-            .arg("create-release-keystore")
-            .arg(&project_name)
-            .assert()
-            .success();
+        let cmd = FtwCommand::AndroidKeystore {
+            build_type: FtwBuildType::Debug,
+        };
+        let _ = cmd.process();
         let targets = vec![
             FtwTarget::AndroidLinuxAarch64,
             FtwTarget::AndroidLinuxArmV7,
@@ -1,120 +1,248 @@
 use crate::ftw_build_type::FtwBuildType;
+use crate::ftw_container::{
+    FtwContainerSession, IOS_CROSS_COMPILER_PATH, MACOSX_CROSS_COMPILER_PATH,
+};
 use crate::ftw_error::FtwError;
+use crate::ftw_fingerprint::{FtwFingerprint, FINGERPRINT_FILE};
+use crate::ftw_keystore::KEYSTORE_PASSWORD_ENV;
 use crate::ftw_target::FtwTarget;
 use crate::traits::{
     Compiler, Runner, ToAppExt, ToCliArg, ToExportArg, ToExportName, ToLibExt, ToLibPrefix,
 };
 use crate::util;
 use command_macros::cmd;
-use fs_extra::dir::CopyOptions;
-use fs_extra::{move_items, remove_items};
-use std::path::Path;
+use std::fs::copy;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use walkdir::WalkDir;
 
 pub enum FtwCompiler {
     Local {
+        root: PathBuf,
         target: FtwTarget,
         build_type: FtwBuildType,
+        godot_executable: String,
     },
     Cross {
+        root: PathBuf,
         target: FtwTarget,
         build_type: FtwBuildType,
+        session: Rc<FtwContainerSession>,
     },
 }
 
-const DOCKER_IMAGE: &str = "macalimlim/godot-rust-cross-compiler:0.8.0";
-const MACOSX_CROSS_COMPILER_PATH: &str = "/opt/macosx-build-tools/cross-compiler";
-const MIN_MACOSX_SDK_VERSION: &str = "11.3";
-const MIN_OSXCROSS_TARGET_VERSION: &str = "20.4";
-const IOS_CROSS_COMPILER_PATH: &str = "/opt/ios-build-tools/cross-compiler";
-const MIN_IOS_SDK_VERSION: &str = "14.5";
-const SHELL: &str = "/bin/bash";
+#[rustfmt::skip::macros(format)]
+impl FtwCompiler {
+    fn get_root_target_and_build_type(&self) -> (&Path, FtwTarget, FtwBuildType) {
+        match self {
+            FtwCompiler::Local {
+                root,
+                target,
+                build_type,
+                godot_executable: _,
+            }
+            | FtwCompiler::Cross {
+                root,
+                target,
+                build_type,
+                session: _,
+            } => (root, *target, *build_type),
+        }
+    }
+
+    /// Copies the cargo artifact into `lib/<triple>/` of the project at `root`
+    /// unless the fingerprint of the last installed artifact shows that it did
+    /// not change, returns `false` in that case
+    fn install_lib(
+        root: &Path,
+        target: FtwTarget,
+        build_type: FtwBuildType,
+    ) -> Result<bool, FtwError> {
+        let crate_name = util::get_crate_name_from_path(&root.join("rust"))?;
+        let target_cli_arg = target.to_cli_arg();
+        let target_lib_ext = target.to_lib_ext();
+        let target_lib_prefix = target.to_lib_prefix();
+        let lib_file = format!("{target_lib_prefix}{crate_name}.{target_lib_ext}");
+        let source_path = root
+            .join("target")
+            .join(&target_cli_arg)
+            .join(build_type.to_string())
+            .join(&lib_file);
+        let source_path = source_path.as_path();
+        let target_path = root.join("lib").join(&target_cli_arg);
+        let lib = target_path.join(&lib_file);
+        let fingerprint_file = target_path.join(FINGERPRINT_FILE);
+        let last_fingerprint = FtwFingerprint::load(&fingerprint_file).filter(|_| lib.exists());
+        let modified = FtwFingerprint::get_modified(source_path)?;
+        if let Some(last_fingerprint) = &last_fingerprint {
+            if last_fingerprint.build_type == build_type && last_fingerprint.modified == modified {
+                return Ok(false);
+            }
+        }
+        let fingerprint = FtwFingerprint::from_path(source_path, build_type)?;
+        let is_same_artifact = last_fingerprint
+            .is_some_and(|last_fingerprint| last_fingerprint.is_same_artifact(&fingerprint));
+        if !is_same_artifact {
+            copy(source_path, &lib)?;
+        }
+        fingerprint.save(&fingerprint_file)?;
+        Ok(!is_same_artifact)
+    }
+
+    /// The path of the exported game relative to the project
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the crate name cannot be read
+    pub fn get_export_path(&self) -> Result<String, FtwError> {
+        let (root, target, build_type) = self.get_root_target_and_build_type();
+        let crate_name = util::get_crate_name_from_path(&root.join("rust"))?;
+        let target_cli_arg = target.to_cli_arg();
+        let target_app_ext = target.to_app_ext();
+        Ok(format!("bin/{target_cli_arg}/{crate_name}.{build_type}.{target_cli_arg}{target_app_ext}"))
+    }
+
+    #[must_use]
+    pub fn get_godot_version(&self) -> Option<String> {
+        match self {
+            FtwCompiler::Local {
+                root: _,
+                target: _,
+                build_type: _,
+                godot_executable,
+            } => util::get_godot_version(godot_executable),
+            FtwCompiler::Cross {
+                root: _,
+                target: _,
+                build_type: _,
+                session,
+            } => {
+                let output = session.exec_output(&[], "godot_headless --version").ok()?;
+                let version = output.lines().last()?.trim().to_string();
+                (!version.is_empty()).then_some(version)
+            }
+        }
+    }
+
+    /// Checks if the exported game is newer than the installed library and
+    /// every file of the godot project, the manifest and fingerprint next to
+    /// the library are rewritten on every build so they are not compared
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the project files cannot be read
+    pub fn is_exported_game_up_to_date(&self) -> Result<bool, FtwError> {
+        let (root, target, _) = self.get_root_target_and_build_type();
+        let export_path = root.join(self.get_export_path()?);
+        let export_path = export_path.as_path();
+        if !export_path.exists() {
+            return Ok(false);
+        }
+        let exported = FtwFingerprint::get_modified(export_path)?;
+        let crate_name = util::get_crate_name_from_path(&root.join("rust"))?;
+        let target_cli_arg = target.to_cli_arg();
+        let target_lib_prefix = target.to_lib_prefix();
+        let target_lib_ext = target.to_lib_ext();
+        let lib = format!("lib/{target_cli_arg}/{target_lib_prefix}{crate_name}.{target_lib_ext}");
+        let godot_entries = WalkDir::new(root.join("godot"))
+            .into_iter()
+            .filter_entry(|entry| entry.file_name() != ".import");
+        for entry in WalkDir::new(root.join(lib))
+            .into_iter()
+            .chain(godot_entries)
+        {
+            let entry = entry?;
+            if entry.file_type().is_file() && FtwFingerprint::get_modified(entry.path())? > exported
+            {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
 
 #[rustfmt::skip::macros(cmd, format)]
 impl Compiler for FtwCompiler {
     fn clean(&self) -> Result<(), FtwError> {
         match self {
             FtwCompiler::Local {
+                root,
                 target: _,
                 build_type: _,
-            } => cmd!(cargo clean).run(),
+                godot_executable: _,
+            } => cmd!(cargo clean).current_dir(root).run(),
             FtwCompiler::Cross {
+                root: _,
                 target: _,
                 build_type: _,
-            } => {
-                let current_dir = Path::new(".").canonicalize()?;
-                let current_dir_display = current_dir.display();
-                let volume_mount = format!("{current_dir_display}:/build");
-                cmd!(docker run ("-v") (volume_mount)
-                     (DOCKER_IMAGE) (SHELL) ("-c")
-                     ("cargo clean ; rm -rf godot/.import"))
-                .run()
-            }
+                session,
+            } => session.exec(&[], "cargo clean ; rm -rf godot/.import"),
         }
     }
 
-    fn build(&self) -> Result<(), FtwError> {
+    fn build(&self) -> Result<bool, FtwError> {
         match self {
-            FtwCompiler::Local { target, build_type } => {
-                let crate_name = util::get_crate_name_from_path("./rust/")?;
+            FtwCompiler::Local {
+                root,
+                target,
+                build_type,
+                godot_executable: _,
+            } => {
                 let target_cli_arg = target.to_cli_arg();
                 let build_type_cli_arg = build_type.to_cli_arg();
-                let target_lib_ext = target.to_lib_ext();
-                let build_type_string = build_type.to_string().to_lowercase();
-                let target_lib_prefix = target.to_lib_prefix();
-                let source_path = format!("./target/{target_cli_arg}/{build_type_string}/{target_lib_prefix}{crate_name}.{target_lib_ext}");
-                let target_path = format!("./lib/{target_cli_arg}");
-                cmd!(cargo build ("--target") (target_cli_arg) if (build_type.is_release()) { (build_type_cli_arg) }).run()?;
-                let lib = format!("{target_path}/{target_lib_prefix}{crate_name}.{target_lib_ext}");
-                if Path::new(&lib).exists() {
-                    let target_lib_files = [lib];
-                    remove_items(&target_lib_files)?;
+                let status = cmd!(cargo build ("--target") (target_cli_arg) if (build_type.is_release()) { (build_type_cli_arg) }).current_dir(root).status()?;
+                if !status.success() {
+                    return Err(FtwError::BuildError(*target));
                 }
-                let options = CopyOptions::new();
-                let source_paths = [source_path];
-                move_items(&source_paths, target_path, &options)?;
-                Ok(())
+                FtwCompiler::install_lib(root, *target, *build_type)
             }
-            FtwCompiler::Cross { target, build_type } => {
+            FtwCompiler::Cross {
+                root,
+                target,
+                build_type,
+                session,
+            } => {
                 let target_cli_arg = target.to_cli_arg();
                 let build_type_cli_arg = build_type.to_cli_arg();
-                let target_lib_ext = target.to_lib_ext();
-                let cargo_build_cmd = format!("cargo build --target {target_cli_arg} {build_type_cli_arg} ; mv -b ./target/{target_cli_arg}/{build_type}/*.{target_lib_ext} ./lib/{target_cli_arg}");
-                let current_dir = Path::new(".").canonicalize()?;
-                let current_dir_display = current_dir.display();
-                let volume_mount = format!("{current_dir_display}:/build");
-                let macosx_sdk_version_output = cmd!(docker run (DOCKER_IMAGE) ("/bin/bash") ("-c") ("echo $MACOSX_SDK_VERSION"))
-                    .output()?;
-                let macosx_sdk_version = String::from_utf8(macosx_sdk_version_output.stdout)
-                    .unwrap_or(String::from(MIN_MACOSX_SDK_VERSION));
-                let macosx_sdk_version = macosx_sdk_version.trim();
+                let cargo_build_cmd =
+                    format!("cargo build --target {target_cli_arg} {build_type_cli_arg}");
+                let sdk_metadata = session.get_sdk_metadata()?;
+                let macosx_sdk_version = &sdk_metadata.macosx_sdk_version;
                 let macosx_c_include_path = format!("C_INCLUDE_PATH={MACOSX_CROSS_COMPILER_PATH}/SDK/MacOSX{macosx_sdk_version}.sdk/usr/include");
-                let osxcross_target_output = cmd!(docker run (DOCKER_IMAGE) (SHELL) ("-c") ("{MACOSX_CROSS_COMPILER_PATH}/bin/osxcross-conf | grep OSXCROSS_TARGET= | sed 's/export OSXCROSS_TARGET=darwin//g'"))
-                    .output()?;
-                let osxcross_target_version = String::from_utf8(osxcross_target_output.stdout)
-                    .unwrap_or(String::from(MIN_OSXCROSS_TARGET_VERSION));
+                let osxcross_target_version = &sdk_metadata.osxcross_target_version;
                 let macosx_cc = format!("CC={MACOSX_CROSS_COMPILER_PATH}/bin/{target_cli_arg}{osxcross_target_version}-cc");
-                let ios_sdk_version_output =
-                    cmd!(docker run (DOCKER_IMAGE) ("/bin/bash") ("-c") ("echo $IOS_SDK_VERSION"))
-                        .output()?;
-                let ios_sdk_version = String::from_utf8(ios_sdk_version_output.stdout)
-                    .unwrap_or(String::from(MIN_IOS_SDK_VERSION));
-                let ios_sdk_version = ios_sdk_version.trim();
+                let ios_sdk_version = &sdk_metadata.ios_sdk_version;
                 let ios_c_include_path = format!("C_INCLUDE_PATH={IOS_CROSS_COMPILER_PATH}/SDK/iPhoneOS{ios_sdk_version}.sdk/usr/include");
                 let ios_ld_library_path = format!("LD_LIBRARY_PATH={IOS_CROSS_COMPILER_PATH}/lib");
-                cmd!(docker run ("-v") (volume_mount)
-                     if (target == &FtwTarget::WindowsX86_64Gnu || target == &FtwTarget::WindowsX86_64Msvc) {("-e") ("C_INCLUDE_PATH=/usr/x86_64-w64-mingw32/include")}
-                     if (target == &FtwTarget::MacOsAarch64 || target == &FtwTarget::MacOsX86_64) {("-e") (macosx_cc) ("-e") (macosx_c_include_path)}
-                     if (target == &FtwTarget::IosAarch64) {("-e") (ios_c_include_path) ("-e") (ios_ld_library_path)}
-                     (DOCKER_IMAGE) (SHELL) ("-c")
-                     (cargo_build_cmd)).run()
+                let envs = match target {
+                    FtwTarget::WindowsX86_64Gnu | FtwTarget::WindowsX86_64Msvc => {
+                        vec![String::from(
+                            "C_INCLUDE_PATH=/usr/x86_64-w64-mingw32/include",
+                        )]
+                    }
+                    FtwTarget::MacOsAarch64 | FtwTarget::MacOsX86_64 => {
+                        vec![macosx_cc, macosx_c_include_path]
+                    }
+                    FtwTarget::IosAarch64 => vec![ios_c_include_path, ios_ld_library_path],
+                    _ => vec![],
+                };
+                if !session.exec_status(&envs, &cargo_build_cmd)? {
+                    return Err(FtwError::BuildError(*target));
+                }
+                FtwCompiler::install_lib(root, *target, *build_type)
             }
         }
     }
 
     fn export(&self) -> Result<(), FtwError> {
         match self {
-            FtwCompiler::Local { target, build_type } => {
-                let crate_name = util::get_crate_name_from_path("./rust/")?;
+            FtwCompiler::Local {
+                root,
+                target,
+                build_type,
+                godot_executable,
+            } => {
+                let crate_name = util::get_crate_name_from_path(&root.join("rust"))?;
                 let target_cli_arg = target.to_cli_arg();
                 let target_export_name = target.to_export_name();
                 let build_type_export_arg = build_type.to_export_arg();
@@ -122,14 +250,17 @@ impl Compiler for FtwCompiler {
                 let target_app_ext = target.to_app_ext();
                 let export_name = format!("{target_export_name}.{target_cli_arg}.{build_type}");
                 let export_path = format!("../bin/{target_cli_arg}/{crate_name}.{build_type}.{target_cli_arg}{target_app_ext}");
-                let current_platform = util::get_current_platform().parse().unwrap_or_default();
-                let godot_executable = util::get_godot_exe_for_exporting(current_platform);
                 cmd!((godot_executable.as_str())(build_type_export_arg)(export_name)(export_path))
-                    .current_dir("./godot")
+                    .current_dir(root.join("godot"))
                     .run()
             }
-            FtwCompiler::Cross { target, build_type } => {
-                let crate_name = util::get_crate_name_from_path("./rust/")?;
+            FtwCompiler::Cross {
+                root,
+                target,
+                build_type,
+                session,
+            } => {
+                let crate_name = util::get_crate_name_from_path(&root.join("rust"))?;
                 let target_cli_arg = target.to_cli_arg();
                 let target_export_name = target.to_export_name();
                 let build_type = build_type.to_string().to_lowercase();
@@ -138,13 +269,12 @@ impl Compiler for FtwCompiler {
                 let export_path = format!("../bin/{target_cli_arg}/{crate_name}.{build_type}.{target_cli_arg}{target_app_ext}");
                 let godot_export_cmd =
                     format!("cd godot/ ; godot_headless --export '{export_name}' {export_path}");
-                let current_dir = Path::new(".").canonicalize()?;
-                let current_dir_display = current_dir.display();
-                let volume_mount = format!("{current_dir_display}:/build");
-                cmd!(docker run ("-v") (volume_mount)
-                    (DOCKER_IMAGE) (SHELL) ("-c")
-                    (godot_export_cmd))
-                .run()
+                let envs = if target.is_android() {
+                    vec![KEYSTORE_PASSWORD_ENV.to_string()]
+                } else {
+                    vec![]
+                };
+                session.exec(&envs, &godot_export_cmd)
             }
         }
     }
@@ -1,11 +1,25 @@
 use crate::ftw_build_type::FtwBuildType;
 use crate::ftw_compiler::FtwCompiler;
+use crate::ftw_container::{
+    FtwContainer, FtwContainerSession, CONTAINER_EXE, CONTAINER_IMAGE, CONTAINER_USER,
+};
 use crate::ftw_target::FtwTarget;
+use crate::util;
 use ini::{Ini, Properties};
+use std::path::Path;
+use std::rc::Rc;
 
 pub const GODOT_EXE: &str = "godot3";
 pub const GODOT_HEADLESS_EXE: &str = "godot3-headless";
 pub const GODOT_SERVER_EXE: &str = "godot3-server";
+const PATH_KEYS: [&str; 6] = [
+    "godot-exe",
+    "godot-headless-exe",
+    "godot-server-exe",
+    "container-exe",
+    "package-extra-files",
+    "test-runner",
+];
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct FtwConfiguration {
@@ -13,12 +27,24 @@ pub struct FtwConfiguration {
     pub godot_headless_executable: String,
     pub godot_server_executable: String,
     pub enable_cross_compilation: bool,
+    pub container: FtwContainer,
+    pub package_extra_files: Vec<String>,
+    pub test_runner: String,
+    pub test_runner_args: Vec<String>,
 }
 
 impl FtwConfiguration {
+    /// The configuration in the `.ftw` file of the current directory
     #[must_use]
     pub fn new() -> Self {
-        let ini = Ini::load_from_file(".ftw").unwrap_or_default();
+        FtwConfiguration::from_root(Path::new("."))
+    }
+
+    /// The configuration in the `.ftw` file of the project at `root`, the
+    /// defaults are used for what is not in there
+    #[must_use]
+    pub fn from_root(root: &Path) -> Self {
+        let ini = Ini::load_from_file(root.join(".ftw")).unwrap_or_default();
         let default_properties = Properties::new();
         let ftw_section = ini.section(Some("ftw")).unwrap_or(&default_properties);
         let exe_key_default_pairs = [
@@ -26,30 +52,92 @@ impl FtwConfiguration {
             ("godot-headless-exe", GODOT_HEADLESS_EXE),
             ("godot-server-exe", GODOT_SERVER_EXE),
             ("enable-cross-compilation", "false"),
+            ("container-exe", CONTAINER_EXE),
+            ("container-image", CONTAINER_IMAGE),
+            ("container-run-args", ""),
+            ("container-user", CONTAINER_USER),
+            ("container-mount-cargo-caches", "true"),
+            ("container-cargo-home", ""),
+            ("package-extra-files", ""),
+            ("test-runner", ""),
+            ("test-runner-args", ""),
         ];
         let keys: Vec<String> = exe_key_default_pairs
             .iter()
-            .map(|(key, def)| ftw_section.get(key).unwrap_or(def).replace('\\', "/"))
+            .map(|(key, def)| {
+                let value = ftw_section.get(key).unwrap_or(def);
+                if PATH_KEYS.contains(key) {
+                    value.replace('\\', "/")
+                } else {
+                    value.to_string()
+                }
+            })
             .collect();
         match keys.as_slice() {
-            [godot_exe, godot_headless_exe, godot_server_exe, enable_cross_compilation] => {
+            [godot_exe, godot_headless_exe, godot_server_exe, enable_cross_compilation, container_exe, container_image, container_run_args, container_user, container_mount_cargo_caches, container_cargo_home, package_extra_files, test_runner, test_runner_args] => {
                 FtwConfiguration {
                     godot_executable: godot_exe.to_string(),
                     godot_headless_executable: godot_headless_exe.to_string(),
                     godot_server_executable: godot_server_exe.to_string(),
                     enable_cross_compilation: enable_cross_compilation == "true",
+                    container: FtwContainer {
+                        executable: container_exe.to_string(),
+                        image: container_image.to_string(),
+                        run_args: container_run_args
+                            .split_whitespace()
+                            .map(String::from)
+                            .collect(),
+                        user: container_user.to_string(),
+                        mount_cargo_caches: container_mount_cargo_caches == "true",
+                        cargo_home: container_cargo_home.to_string(),
+                    },
+                    package_extra_files: package_extra_files
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|file| !file.is_empty())
+                        .map(String::from)
+                        .collect(),
+                    test_runner: test_runner.to_string(),
+                    test_runner_args: test_runner_args
+                        .split_whitespace()
+                        .map(String::from)
+                        .collect(),
                 }
             }
             _ => unreachable!(),
         }
     }
 
+    /// The container session of the project at `root`
+    #[must_use]
+    pub fn get_container_session(&self, root: &Path) -> Rc<FtwContainerSession> {
+        Rc::new(FtwContainerSession::new(self.container.clone(), root))
+    }
+
+    /// The compiler of the project at `root`
     #[must_use]
-    pub fn get_compiler(&self, target: FtwTarget, build_type: FtwBuildType) -> FtwCompiler {
+    pub fn get_compiler(
+        &self,
+        root: &Path,
+        target: FtwTarget,
+        build_type: FtwBuildType,
+        session: &Rc<FtwContainerSession>,
+    ) -> FtwCompiler {
         if self.enable_cross_compilation {
-            FtwCompiler::Cross { target, build_type }
+            FtwCompiler::Cross {
+                root: root.to_path_buf(),
+                target,
+                build_type,
+                session: Rc::clone(session),
+            }
         } else {
-            FtwCompiler::Local { target, build_type }
+            let current_platform = util::get_current_platform().parse().unwrap_or_default();
+            FtwCompiler::Local {
+                root: root.to_path_buf(),
+                target,
+                build_type,
+                godot_executable: util::get_godot_exe_for_exporting(self, current_platform),
+            }
         }
     }
 }
@@ -61,6 +149,10 @@ impl Default for FtwConfiguration {
             godot_headless_executable: GODOT_HEADLESS_EXE.to_string(),
             godot_server_executable: GODOT_SERVER_EXE.to_string(),
             enable_cross_compilation: false,
+            container: FtwContainer::default(),
+            package_extra_files: vec![],
+            test_runner: String::new(),
+            test_runner_args: vec![],
         }
     }
 }
@@ -68,6 +160,7 @@ impl Default for FtwConfiguration {
 #[cfg(test)]
 mod ftw_configuration_tests {
     use super::*;
+    use std::{env, fs, io};
 
     #[test]
     fn test_default() {
@@ -76,7 +169,37 @@ mod ftw_configuration_tests {
             godot_headless_executable: GODOT_HEADLESS_EXE.to_string(),
             godot_server_executable: GODOT_SERVER_EXE.to_string(),
             enable_cross_compilation: false,
+            container: FtwContainer::default(),
+            package_extra_files: vec![],
+            test_runner: String::new(),
+            test_runner_args: vec![],
         };
         assert_eq!(FtwConfiguration::default(), cfg);
     }
+
+    #[test]
+    fn test_from_root() -> Result<(), io::Error> {
+        let root = env::temp_dir().join(format!("ftw-configuration-{}", std::process::id()));
+        fs::create_dir_all(&root)?;
+        assert_eq!(
+            FtwConfiguration::default(),
+            FtwConfiguration::from_root(&root)
+        );
+        fs::write(
+            root.join(".ftw"),
+            "[ftw]\ngodot-exe=C:\\\\godot\\\\godot.exe\ntest-runner=tests\\\\run.gd\ncontainer-run-args=-e FOO=a\\\\b\n",
+        )?;
+        let cfg = FtwConfiguration {
+            godot_executable: "C:/godot/godot.exe".to_string(),
+            container: FtwContainer {
+                run_args: vec!["-e".to_string(), "FOO=a\\b".to_string()],
+                ..FtwContainer::default()
+            },
+            test_runner: "tests/run.gd".to_string(),
+            ..FtwConfiguration::default()
+        };
+        assert_eq!(cfg, FtwConfiguration::from_root(&root));
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
 }
@@ -0,0 +1,657 @@
+use crate::ftw_error::FtwError;
+use crate::traits::Runner;
+use crate::util;
+use std::cell::RefCell;
+use std::env;
+use std::fs::create_dir_all;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, Once};
+
+pub const CONTAINER_EXE: &str = "docker";
+pub const CONTAINER_IMAGE: &str = "macalimlim/godot-rust-cross-compiler:0.8.0";
+pub const CONTAINER_USER: &str = "host";
+pub const CONTAINER_CARGO_HOME: &str = "/usr/local/cargo";
+const CONTAINER_USER_HOME: &str = "/tmp";
+const CONTAINER_USER_CARGO_HOME: &str = "/tmp/cargo";
+const CONTAINER_WORKDIR: &str = "/build";
+const SHELL: &str = "/bin/bash";
+const CARGO_CACHES: [&str; 2] = ["registry", "git"];
+pub const MACOSX_CROSS_COMPILER_PATH: &str = "/opt/macosx-build-tools/cross-compiler";
+const MIN_MACOSX_SDK_VERSION: &str = "11.3";
+const MIN_OSXCROSS_TARGET_VERSION: &str = "20.4";
+pub const IOS_CROSS_COMPILER_PATH: &str = "/opt/ios-build-tools/cross-compiler";
+const MIN_IOS_SDK_VERSION: &str = "14.5";
+
+static RUNNING_CONTAINERS: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+static CTRL_C_HANDLER: Once = Once::new();
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwContainer {
+    pub executable: String,
+    pub image: String,
+    pub run_args: Vec<String>,
+    pub user: String,
+    pub mount_cargo_caches: bool,
+    pub cargo_home: String,
+}
+
+impl FtwContainer {
+    /// Creates a command that starts a detached container with the project at
+    /// `root` mounted and the configured user, caches and extra run
+    /// arguments, printing the container id
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `root` cannot be resolved
+    pub fn start_command(&self, root: &Path) -> Result<Command, FtwError> {
+        let root = root.canonicalize()?;
+        let root_display = root.display();
+        let volume_mount = format!("{root_display}:{CONTAINER_WORKDIR}");
+        let mut command = Command::new(&self.executable);
+        command
+            .arg("run")
+            .arg("-d")
+            .arg("--rm")
+            .arg("-v")
+            .arg(volume_mount)
+            .arg("-w")
+            .arg(CONTAINER_WORKDIR);
+        let user = self.get_user();
+        command.args(self.get_user_args(user.as_deref()));
+        if !self.cargo_home.is_empty() {
+            command
+                .arg("-e")
+                .arg(format!("CARGO_HOME={}", self.cargo_home));
+        }
+        if self.mount_cargo_caches {
+            let host_cargo_home = FtwContainer::get_host_cargo_home()?;
+            let cargo_home = self.get_cargo_home(user.as_deref());
+            for cache_mount in FtwContainer::get_cargo_cache_mounts(&host_cargo_home, cargo_home)? {
+                command.arg("-v").arg(cache_mount);
+            }
+        }
+        command
+            .args(&self.run_args)
+            .arg(&self.image)
+            .arg(SHELL)
+            .arg("-c")
+            .arg("sleep infinity");
+        Ok(command)
+    }
+
+    /// Creates a command that runs `script` inside the started container
+    /// `id` with the given environment variables
+    #[must_use]
+    pub fn exec_command(&self, id: &str, envs: &[String], script: &str) -> Command {
+        let mut command = Command::new(&self.executable);
+        command.arg("exec");
+        for env in envs {
+            command.arg("-e").arg(env);
+        }
+        command.arg(id).arg(SHELL).arg("-c").arg(script);
+        command
+    }
+
+    /// Creates a command that stops and removes the started container `id`
+    #[must_use]
+    pub fn stop_command(&self, id: &str) -> Command {
+        let mut command = Command::new(&self.executable);
+        command.arg("rm").arg("-f").arg(id);
+        command
+    }
+
+    /// Resolves the `--user` to run as, `host` being the uid/gid of the
+    /// invoking user and an empty value leaving it to the image
+    #[must_use]
+    pub fn get_user(&self) -> Option<String> {
+        match self.user.as_str() {
+            "" => None,
+            CONTAINER_USER => FtwContainer::get_host_user(),
+            user => Some(user.to_string()),
+        }
+    }
+
+    fn is_podman(&self) -> bool {
+        Path::new(&self.executable)
+            .file_stem()
+            .and_then(|file_stem| file_stem.to_str())
+            .is_some_and(|file_stem| file_stem.starts_with("podman"))
+    }
+
+    fn is_root_user(user: &str) -> bool {
+        let name = user.split(':').next().unwrap_or_default();
+        name == "root" || name == "0"
+    }
+
+    /// The `run` arguments for `user`, rootless podman maps a host uid to a
+    /// subuid so the host user is kept with `--userns=keep-id` instead of
+    /// `--user`, a non-root user also gets a writable `HOME` and (unless one
+    /// is configured) `CARGO_HOME` since the ones of the image are owned by
+    /// root
+    fn get_user_args(&self, user: Option<&str>) -> Vec<String> {
+        let Some(user) = user else {
+            return vec![];
+        };
+        let is_root_user = FtwContainer::is_root_user(user);
+        let mut args = if self.user == CONTAINER_USER && self.is_podman() && !is_root_user {
+            vec![String::from("--userns=keep-id")]
+        } else {
+            vec![String::from("--user"), user.to_string()]
+        };
+        if !is_root_user {
+            args.extend([String::from("-e"), format!("HOME={CONTAINER_USER_HOME}")]);
+        }
+        if !is_root_user && self.cargo_home.is_empty() {
+            args.extend([
+                String::from("-e"),
+                format!("CARGO_HOME={CONTAINER_USER_CARGO_HOME}"),
+            ]);
+            if self.mount_cargo_caches {
+                // the runtime would create the parent of the cache mounts owned by root
+                args.extend([
+                    String::from("--tmpfs"),
+                    format!("{CONTAINER_USER_CARGO_HOME}:exec,mode=1777"),
+                ]);
+            }
+        }
+        args
+    }
+
+    /// The `CARGO_HOME` inside the container when running as `user`, the
+    /// configured one if any, otherwise the one of the image or a writable one
+    /// for a non-root user
+    fn get_cargo_home(&self, user: Option<&str>) -> &str {
+        match user {
+            _ if !self.cargo_home.is_empty() => &self.cargo_home,
+            Some(user) if !FtwContainer::is_root_user(user) => CONTAINER_USER_CARGO_HOME,
+            _ => CONTAINER_CARGO_HOME,
+        }
+    }
+
+    #[cfg(unix)]
+    fn get_host_user() -> Option<String> {
+        // SAFETY: getuid and getgid are always successful and have no side effects
+        let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+        Some(format!("{uid}:{gid}"))
+    }
+
+    #[cfg(not(unix))]
+    fn get_host_user() -> Option<String> {
+        None
+    }
+
+    fn get_host_cargo_home() -> Result<PathBuf, FtwError> {
+        match env::var_os("CARGO_HOME") {
+            Some(cargo_home) => Ok(PathBuf::from(cargo_home)),
+            None => env::var_os("HOME")
+                .or_else(|| env::var_os("USERPROFILE"))
+                .map(|home| Path::new(&home).join(".cargo"))
+                .ok_or(FtwError::PathError),
+        }
+    }
+
+    fn get_cargo_cache_mounts(
+        host_cargo_home: &Path,
+        cargo_home: &str,
+    ) -> Result<Vec<String>, FtwError> {
+        CARGO_CACHES
+            .iter()
+            .map(|cache| {
+                // the runtime would create a missing directory owned by root
+                let host_cache = host_cargo_home.join(cache);
+                create_dir_all(&host_cache)?;
+                let host_cache_display = host_cache.display();
+                Ok(format!("{host_cache_display}:{cargo_home}/{cache}"))
+            })
+            .collect()
+    }
+}
+
+impl Default for FtwContainer {
+    fn default() -> Self {
+        FtwContainer {
+            executable: CONTAINER_EXE.to_string(),
+            image: CONTAINER_IMAGE.to_string(),
+            run_args: vec![],
+            user: CONTAINER_USER.to_string(),
+            mount_cargo_caches: true,
+            cargo_home: String::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwSdkMetadata {
+    pub macosx_sdk_version: String,
+    pub osxcross_target_version: String,
+    pub ios_sdk_version: String,
+}
+
+impl FtwSdkMetadata {
+    /// Prints a `KEY=value` line per version, so a version that cannot be
+    /// queried (e.g. an image without osxcross) is only an empty value
+    fn query_script() -> String {
+        format!("echo MACOSX_SDK_VERSION=$MACOSX_SDK_VERSION ; echo OSXCROSS_TARGET_VERSION=$({MACOSX_CROSS_COMPILER_PATH}/bin/osxcross-conf | grep OSXCROSS_TARGET= | sed 's/export OSXCROSS_TARGET=darwin//g') ; echo IOS_SDK_VERSION=$IOS_SDK_VERSION")
+    }
+
+    fn from_query_output(output: &str) -> Self {
+        let get_or = |key: &str, default: &str| {
+            output
+                .lines()
+                .filter_map(|line| line.trim().split_once('='))
+                .find(|(line_key, value)| *line_key == key && !value.trim().is_empty())
+                .map_or(default.to_string(), |(_, value)| value.trim().to_string())
+        };
+        FtwSdkMetadata {
+            macosx_sdk_version: get_or("MACOSX_SDK_VERSION", MIN_MACOSX_SDK_VERSION),
+            osxcross_target_version: get_or("OSXCROSS_TARGET_VERSION", MIN_OSXCROSS_TARGET_VERSION),
+            ios_sdk_version: get_or("IOS_SDK_VERSION", MIN_IOS_SDK_VERSION),
+        }
+    }
+}
+
+/// A build container of the project at `root` that is started on first use,
+/// shared by every target of a single ftw invocation and removed when dropped
+/// or on Ctrl-C
+#[derive(Debug, Eq, PartialEq)]
+pub struct FtwContainerSession {
+    container: FtwContainer,
+    root: PathBuf,
+    id: RefCell<Option<String>>,
+    sdk_metadata: RefCell<Option<FtwSdkMetadata>>,
+}
+
+impl FtwContainerSession {
+    #[must_use]
+    pub fn new(container: FtwContainer, root: &Path) -> Self {
+        FtwContainerSession {
+            container,
+            root: root.to_path_buf(),
+            id: RefCell::new(None),
+            sdk_metadata: RefCell::new(None),
+        }
+    }
+
+    fn get_id(&self) -> Result<String, FtwError> {
+        if let Some(id) = self.id.borrow().as_ref() {
+            return Ok(id.clone());
+        }
+        let output = self.container.start_command(&self.root)?.output()?;
+        let id = String::from_utf8(output.stdout)
+            .map_err(|_| FtwError::StringConversionError)?
+            .trim()
+            .to_string();
+        if !output.status.success() || id.is_empty() {
+            return Err(FtwError::ContainerStartError);
+        }
+        FtwContainerSession::register(&self.container.executable, &id);
+        *self.id.borrow_mut() = Some(id.clone());
+        Ok(id)
+    }
+
+    fn register(executable: &str, id: &str) {
+        CTRL_C_HANDLER.call_once(|| util::on_ctrl_c(FtwContainerSession::stop_all));
+        if let Ok(mut running_containers) = RUNNING_CONTAINERS.lock() {
+            running_containers.push((executable.to_string(), id.to_string()));
+        }
+    }
+
+    fn stop_all() {
+        if let Ok(mut running_containers) = RUNNING_CONTAINERS.lock() {
+            for (executable, id) in running_containers.drain(..) {
+                drop(
+                    Command::new(executable)
+                        .arg("rm")
+                        .arg("-f")
+                        .arg(id)
+                        .output(),
+                );
+            }
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the container cannot be started or `script` cannot be executed
+    pub fn exec(&self, envs: &[String], script: &str) -> Result<(), FtwError> {
+        let id = self.get_id()?;
+        self.container.exec_command(&id, envs, script).run()
+    }
+
+    /// Executes `script` and returns `true` if it exited successfully
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the container cannot be started or `script` cannot be executed
+    pub fn exec_status(&self, envs: &[String], script: &str) -> Result<bool, FtwError> {
+        let id = self.get_id()?;
+        let status = self.container.exec_command(&id, envs, script).status()?;
+        Ok(status.success())
+    }
+
+    /// Executes `script` and captures its standard output
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the container cannot be started or `script` cannot be executed
+    pub fn exec_output(&self, envs: &[String], script: &str) -> Result<String, FtwError> {
+        let id = self.get_id()?;
+        let output = self.container.exec_command(&id, envs, script).output()?;
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Queries the SDK versions inside the container once and caches them
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the container cannot be started or queried
+    pub fn get_sdk_metadata(&self) -> Result<FtwSdkMetadata, FtwError> {
+        if let Some(sdk_metadata) = self.sdk_metadata.borrow().as_ref() {
+            return Ok(sdk_metadata.clone());
+        }
+        let output = self.exec_output(&[], &FtwSdkMetadata::query_script())?;
+        let sdk_metadata = FtwSdkMetadata::from_query_output(&output);
+        *self.sdk_metadata.borrow_mut() = Some(sdk_metadata.clone());
+        Ok(sdk_metadata)
+    }
+
+    pub fn stop(&self) {
+        if let Some(id) = self.id.borrow_mut().take() {
+            drop(self.container.stop_command(&id).output());
+            if let Ok(mut running_containers) = RUNNING_CONTAINERS.lock() {
+                running_containers.retain(|(_, running_id)| running_id != &id);
+            }
+        }
+    }
+}
+
+impl Drop for FtwContainerSession {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod ftw_container_tests {
+    use super::*;
+    use std::ffi::OsStr;
+
+    #[test]
+    fn test_default() {
+        let container = FtwContainer {
+            executable: CONTAINER_EXE.to_string(),
+            image: CONTAINER_IMAGE.to_string(),
+            run_args: vec![],
+            user: CONTAINER_USER.to_string(),
+            mount_cargo_caches: true,
+            cargo_home: String::new(),
+        };
+        assert_eq!(FtwContainer::default(), container);
+    }
+
+    #[test]
+    fn test_start_command() -> Result<(), FtwError> {
+        let container = FtwContainer {
+            executable: "podman".to_string(),
+            image: "localhost/cross:dev".to_string(),
+            run_args: vec!["--network".to_string(), "host".to_string()],
+            user: String::new(),
+            mount_cargo_caches: false,
+            cargo_home: String::new(),
+        };
+        let command = container.start_command(Path::new("."))?;
+        let current_dir = Path::new(".").canonicalize()?;
+        let current_dir_display = current_dir.display();
+        let volume_mount = format!("{current_dir_display}:/build");
+        let args: Vec<&OsStr> = command.get_args().collect();
+        let expected_args = [
+            "run",
+            "-d",
+            "--rm",
+            "-v",
+            volume_mount.as_str(),
+            "-w",
+            "/build",
+            "--network",
+            "host",
+            "localhost/cross:dev",
+            "/bin/bash",
+            "-c",
+            "sleep infinity",
+        ];
+        assert_eq!(OsStr::new("podman"), command.get_program());
+        assert_eq!(expected_args.map(OsStr::new).to_vec(), args);
+        Ok(())
+    }
+
+    #[test]
+    fn test_start_command_as_user() -> Result<(), FtwError> {
+        let container = FtwContainer {
+            user: "1000:1000".to_string(),
+            mount_cargo_caches: false,
+            ..FtwContainer::default()
+        };
+        let command = container.start_command(Path::new("."))?;
+        let args: Vec<&OsStr> = command.get_args().collect();
+        let expected_args = [
+            "--user",
+            "1000:1000",
+            "-e",
+            "HOME=/tmp",
+            "-e",
+            "CARGO_HOME=/tmp/cargo",
+        ];
+        assert_eq!(expected_args.map(OsStr::new).to_vec(), &args[7..13]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_start_command_with_cargo_home() -> Result<(), FtwError> {
+        let container = FtwContainer {
+            user: "1000:1000".to_string(),
+            mount_cargo_caches: false,
+            cargo_home: "/opt/cargo".to_string(),
+            ..FtwContainer::default()
+        };
+        let command = container.start_command(Path::new("."))?;
+        let args: Vec<&OsStr> = command.get_args().collect();
+        let expected_args = [
+            "--user",
+            "1000:1000",
+            "-e",
+            "HOME=/tmp",
+            "-e",
+            "CARGO_HOME=/opt/cargo",
+            "macalimlim/godot-rust-cross-compiler:0.8.0",
+        ];
+        assert_eq!(expected_args.map(OsStr::new).to_vec(), &args[7..14]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_user_args() {
+        let container = FtwContainer::default();
+        assert_eq!(Vec::<String>::new(), container.get_user_args(None));
+        assert_eq!(
+            vec!["--user", "root"],
+            container.get_user_args(Some("root"))
+        );
+        assert_eq!(vec!["--user", "0:0"], container.get_user_args(Some("0:0")));
+        let expected_args = [
+            "--user",
+            "1000:1000",
+            "-e",
+            "HOME=/tmp",
+            "-e",
+            "CARGO_HOME=/tmp/cargo",
+            "--tmpfs",
+            "/tmp/cargo:exec,mode=1777",
+        ];
+        assert_eq!(
+            expected_args.to_vec(),
+            container.get_user_args(Some("1000:1000"))
+        );
+        assert_eq!("/tmp/cargo", container.get_cargo_home(Some("1000:1000")));
+        assert_eq!("/usr/local/cargo", container.get_cargo_home(Some("0:0")));
+        assert_eq!("/usr/local/cargo", container.get_cargo_home(None));
+        let container = FtwContainer {
+            cargo_home: "/opt/cargo".to_string(),
+            ..container
+        };
+        assert_eq!(
+            vec!["--user", "1000:1000", "-e", "HOME=/tmp"],
+            container.get_user_args(Some("1000:1000"))
+        );
+        assert_eq!("/opt/cargo", container.get_cargo_home(Some("1000:1000")));
+        assert_eq!("/opt/cargo", container.get_cargo_home(Some("0:0")));
+        assert_eq!("/opt/cargo", container.get_cargo_home(None));
+    }
+
+    #[test]
+    fn test_get_user_args_rootless_podman() {
+        let container = FtwContainer {
+            executable: "/usr/bin/podman".to_string(),
+            mount_cargo_caches: false,
+            ..FtwContainer::default()
+        };
+        let expected_args = [
+            "--userns=keep-id",
+            "-e",
+            "HOME=/tmp",
+            "-e",
+            "CARGO_HOME=/tmp/cargo",
+        ];
+        assert_eq!(
+            expected_args.to_vec(),
+            container.get_user_args(Some("1000:1000"))
+        );
+        // rootful podman does not allow keep-id
+        assert_eq!(vec!["--user", "0:0"], container.get_user_args(Some("0:0")));
+        // an explicit user is passed as it is
+        let container = FtwContainer {
+            user: "1000:1000".to_string(),
+            ..container
+        };
+        assert_eq!(
+            vec!["--user", "1000:1000"],
+            &container.get_user_args(Some("1000:1000"))[..2]
+        );
+    }
+
+    #[test]
+    fn test_get_user() {
+        let container = FtwContainer {
+            user: String::new(),
+            ..FtwContainer::default()
+        };
+        assert_eq!(None, container.get_user());
+        let container = FtwContainer {
+            user: "root".to_string(),
+            ..FtwContainer::default()
+        };
+        assert_eq!(Some("root".to_string()), container.get_user());
+        let host_user = FtwContainer::default().get_user();
+        if cfg!(unix) {
+            assert!(host_user.is_some());
+        } else {
+            assert!(host_user.is_none());
+        }
+    }
+
+    #[test]
+    fn test_get_cargo_cache_mounts() -> Result<(), FtwError> {
+        let host_cargo_home =
+            env::temp_dir().join(format!("ftw-cargo-home-{}", std::process::id()));
+        let mounts = FtwContainer::get_cargo_cache_mounts(&host_cargo_home, CONTAINER_CARGO_HOME)?;
+        let host_cargo_home_display = host_cargo_home.display();
+        assert_eq!(
+            vec![
+                format!("{host_cargo_home_display}/registry:/usr/local/cargo/registry"),
+                format!("{host_cargo_home_display}/git:/usr/local/cargo/git"),
+            ],
+            mounts
+        );
+        assert!(host_cargo_home.join("registry").is_dir());
+        assert!(host_cargo_home.join("git").is_dir());
+        std::fs::remove_dir_all(&host_cargo_home)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_command() {
+        let container = FtwContainer::default();
+        let envs = ["FOO=bar".to_string()];
+        let command = container.exec_command("abc123", &envs, "cargo clean");
+        let args: Vec<&OsStr> = command.get_args().collect();
+        let expected_args = [
+            "exec",
+            "-e",
+            "FOO=bar",
+            "abc123",
+            "/bin/bash",
+            "-c",
+            "cargo clean",
+        ];
+        assert_eq!(OsStr::new("docker"), command.get_program());
+        assert_eq!(expected_args.map(OsStr::new).to_vec(), args);
+    }
+
+    #[test]
+    fn test_stop_command() {
+        let container = FtwContainer::default();
+        let command = container.stop_command("abc123");
+        let args: Vec<&OsStr> = command.get_args().collect();
+        assert_eq!(["rm", "-f", "abc123"].map(OsStr::new).to_vec(), args);
+    }
+
+    #[test]
+    fn test_sdk_metadata_from_query_output() {
+        let sdk_metadata = FtwSdkMetadata::from_query_output(
+            "MACOSX_SDK_VERSION=12.3\nOSXCROSS_TARGET_VERSION=21.4\nIOS_SDK_VERSION=15.0\n",
+        );
+        assert_eq!(
+            FtwSdkMetadata {
+                macosx_sdk_version: "12.3".to_string(),
+                osxcross_target_version: "21.4".to_string(),
+                ios_sdk_version: "15.0".to_string(),
+            },
+            sdk_metadata
+        );
+        let sdk_metadata = FtwSdkMetadata::from_query_output("\n");
+        assert_eq!(
+            FtwSdkMetadata {
+                macosx_sdk_version: MIN_MACOSX_SDK_VERSION.to_string(),
+                osxcross_target_version: MIN_OSXCROSS_TARGET_VERSION.to_string(),
+                ios_sdk_version: MIN_IOS_SDK_VERSION.to_string(),
+            },
+            sdk_metadata
+        );
+        // an image without osxcross
+        let sdk_metadata = FtwSdkMetadata::from_query_output(
+            "MACOSX_SDK_VERSION=\nOSXCROSS_TARGET_VERSION=\nIOS_SDK_VERSION=15.0\n",
+        );
+        assert_eq!(
+            FtwSdkMetadata {
+                macosx_sdk_version: MIN_MACOSX_SDK_VERSION.to_string(),
+                osxcross_target_version: MIN_OSXCROSS_TARGET_VERSION.to_string(),
+                ios_sdk_version: "15.0".to_string(),
+            },
+            sdk_metadata
+        );
+    }
+
+    #[test]
+    fn test_session_start_error() {
+        let container = FtwContainer {
+            executable: "false".to_string(),
+            mount_cargo_caches: false,
+            ..FtwContainer::default()
+        };
+        let session = FtwContainerSession::new(container, Path::new("."));
+        match session.exec(&[], "true") {
+            Err(FtwError::ContainerStartError) => {}
+            _ => unreachable!(),
+        }
+    }
+}
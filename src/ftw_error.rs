@@ -1,3 +1,5 @@
+use crate::ftw_build_type::FtwBuildType;
+use crate::ftw_target::FtwTarget;
 use crate::traits::ToMessage;
 use crate::type_alias::Message;
 use colored::{ColoredString, Colorize};
@@ -7,8 +9,8 @@ use thiserror::Error;
 pub enum FtwError {
     #[error("{0}")]
     Error(#[from] std::io::Error),
-    #[error("Invalid project")]
-    InvalidProject,
+    #[error("Invalid project, {} required path(s) are missing, run ftw check to list them", .0.len())]
+    InvalidProject(Vec<String>),
     #[error("{0}")]
     LiquidError(#[from] liquid_core::Error),
     #[error("Walkdir error")]
@@ -23,6 +25,8 @@ pub enum FtwError {
     TomlError(#[from] toml::de::Error),
     #[error("Missing package name error")]
     MissingPackageNameError,
+    #[error("Missing package version error")]
+    MissingPackageVersionError,
     #[error("Path error")]
     PathError,
     #[error("String conversion error")]
@@ -31,6 +35,58 @@ pub enum FtwError {
     RegexError(#[from] regex::Error),
     #[error("{0}")]
     AnyhowError(#[from] anyhow::Error),
+    #[error("Container could not be started")]
+    ContainerStartError,
+    #[error("{0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("Artifacts could not be verified")]
+    InvalidArtifacts,
+    #[error("{0}")]
+    ZipError(#[from] zip::result::ZipError),
+    #[error("The {0} keystore is missing, create it with ftw android keystore {0}")]
+    MissingKeystore(FtwBuildType),
+    #[error("The release keystore password is missing, set it in the GODOT_ANDROID_KEYSTORE_RELEASE_PASSWORD environment variable")]
+    MissingKeystorePassword,
+    #[error("The scene {0} could not be found")]
+    MissingScene(String),
+    #[error("The library could not be built for {0}")]
+    BuildError(FtwTarget),
+    #[error("The server executable {0} could not be run, set godot-server-exe in .ftw or use Godot 4 as godot-exe")]
+    MissingServerExecutable(String),
+    #[error("The test runner {0} could not be found inside godot/")]
+    MissingTestRunner(String),
+    #[error("{0} test(s) failed")]
+    TestsFailed(usize),
+    #[error("The project directory {0} could not be found")]
+    MissingProjectDirectory(String),
+    #[error("The upgrade to {0} has {} conflict(s), resolve them then write {0} into .tag", .1.len())]
+    UpgradeConflicts(String, Vec<String>),
+    #[error("The property {0} is invalid, use name:type or name:type=default with a rust value type or a godot class")]
+    InvalidProperty(String),
+    #[error("The signal {0} is invalid, use name or name:type,type... with rust value types or godot classes")]
+    InvalidSignal(String),
+    #[error("The scene {0} has no root node")]
+    InvalidScene(String),
+    #[error("The root node of the scene {0} already has a script")]
+    SceneHasScript(String),
+    #[error("The class {0} could not be found, create it with ftw class or ftw attach")]
+    MissingClass(String),
+    #[error("The child {0} is not a godot node type")]
+    InvalidChild(String),
+    #[error("The root node type {1} of the scene {0} is not a godot node type")]
+    InvalidRootNodeType(String, String),
+    #[error("The node {0} could not be found in the scene {1}")]
+    MissingNode(String, String),
+    #[error("The file {0} to package could not be found")]
+    MissingPackageFile(String),
+    #[error("The file {0} to package is outside of the project")]
+    InvalidPackageFile(String),
+    #[error("The files {0} and {1} would have the same path inside the archive")]
+    PackageEntryCollision(String, String),
+    #[error(
+        "The original {0} could not be restored after exporting, restore it from version control"
+    )]
+    GdnlibNotRestored(String),
 }
 
 impl FtwError {
@@ -59,11 +115,8 @@ mod ftw_error_tests {
     #[test]
     fn test_to_message() {
         let error_description = "IO error";
-        let io_error_message = FtwError::Error(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            error_description,
-        ))
-        .to_message();
+        let io_error_message =
+            FtwError::Error(std::io::Error::other(error_description)).to_message();
         let thumbs_down = FtwError::THUMBS_DOWN;
         let styled_error = FtwError::get_styled_error();
         assert_eq!(
@@ -71,9 +124,13 @@ mod ftw_error_tests {
             io_error_message
         );
         //
-        let invalid_project_error_message = FtwError::InvalidProject.to_message();
+        let invalid_project_error_message = FtwError::InvalidProject(vec![
+            "Cargo.toml".to_string(),
+            "rust/src/lib.rs".to_string(),
+        ])
+        .to_message();
         assert_eq!(
-            format!("{thumbs_down} {styled_error} Invalid project"),
+            format!("{thumbs_down} {styled_error} Invalid project, 2 required path(s) are missing, run ftw check to list them"),
             invalid_project_error_message
         );
         //
@@ -112,6 +169,13 @@ mod ftw_error_tests {
         //
         // TODO: cargo edit error
         //
+        let missing_package_version_error_message =
+            FtwError::MissingPackageVersionError.to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} Missing package version error"),
+            missing_package_version_error_message
+        );
+        //
         let path_error_message = FtwError::PathError.to_message();
         assert_eq!(
             format!("{thumbs_down} {styled_error} Path error"),
@@ -133,5 +197,181 @@ mod ftw_error_tests {
         );
         //
         // TODO: anyhow error
+        //
+        let container_start_error_message = FtwError::ContainerStartError.to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} Container could not be started"),
+            container_start_error_message
+        );
+        //
+        // TODO: json error
+        //
+        let invalid_artifacts_error_message = FtwError::InvalidArtifacts.to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} Artifacts could not be verified"),
+            invalid_artifacts_error_message
+        );
+        //
+        let zip_error_message =
+            FtwError::ZipError(zip::result::ZipError::FileNotFound).to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} specified file not found in archive"),
+            zip_error_message
+        );
+        //
+        let missing_keystore_error_message =
+            FtwError::MissingKeystore(FtwBuildType::Debug).to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The debug keystore is missing, create it with ftw android keystore debug"),
+            missing_keystore_error_message
+        );
+        //
+        let missing_keystore_password_error_message =
+            FtwError::MissingKeystorePassword.to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The release keystore password is missing, set it in the GODOT_ANDROID_KEYSTORE_RELEASE_PASSWORD environment variable"),
+            missing_keystore_password_error_message
+        );
+        //
+        let missing_scene_error_message =
+            FtwError::MissingScene("scenes/Level2.tscn".to_string()).to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The scene scenes/Level2.tscn could not be found"),
+            missing_scene_error_message
+        );
+        //
+        let build_error_message = FtwError::BuildError(FtwTarget::LinuxX86_64).to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The library could not be built for x86_64-unknown-linux-gnu"),
+            build_error_message
+        );
+        //
+        let missing_server_executable_error_message =
+            FtwError::MissingServerExecutable("godot3-server".to_string()).to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The server executable godot3-server could not be run, set godot-server-exe in .ftw or use Godot 4 as godot-exe"),
+            missing_server_executable_error_message
+        );
+        //
+        let missing_test_runner_error_message =
+            FtwError::MissingTestRunner("tests/TestRunner.tscn".to_string()).to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The test runner tests/TestRunner.tscn could not be found inside godot/"),
+            missing_test_runner_error_message
+        );
+        //
+        let tests_failed_error_message = FtwError::TestsFailed(2).to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} 2 test(s) failed"),
+            tests_failed_error_message
+        );
+        //
+        let missing_project_directory_error_message =
+            FtwError::MissingProjectDirectory("games/demo".to_string()).to_message();
+        assert_eq!(
+            format!(
+                "{thumbs_down} {styled_error} The project directory games/demo could not be found"
+            ),
+            missing_project_directory_error_message
+        );
+        //
+        let upgrade_conflicts_error_message =
+            FtwError::UpgradeConflicts("v1.5.0".to_string(), vec!["Cargo.toml".to_string()])
+                .to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The upgrade to v1.5.0 has 1 conflict(s), resolve them then write v1.5.0 into .tag"),
+            upgrade_conflicts_error_message
+        );
+        //
+        let invalid_property_error_message =
+            FtwError::InvalidProperty("damage:u128".to_string()).to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The property damage:u128 is invalid, use name:type or name:type=default with a rust value type or a godot class"),
+            invalid_property_error_message
+        );
+        //
+        let invalid_signal_error_message =
+            FtwError::InvalidSignal("hit:u128".to_string()).to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The signal hit:u128 is invalid, use name or name:type,type... with rust value types or godot classes"),
+            invalid_signal_error_message
+        );
+        //
+        let invalid_scene_error_message =
+            FtwError::InvalidScene("godot/scenes/Boss.tscn".to_string()).to_message();
+        assert_eq!(
+            format!(
+                "{thumbs_down} {styled_error} The scene godot/scenes/Boss.tscn has no root node"
+            ),
+            invalid_scene_error_message
+        );
+        //
+        let scene_has_script_error_message =
+            FtwError::SceneHasScript("godot/scenes/Boss.tscn".to_string()).to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The root node of the scene godot/scenes/Boss.tscn already has a script"),
+            scene_has_script_error_message
+        );
+        //
+        let missing_class_error_message =
+            FtwError::MissingClass("rust/src/boss.rs".to_string()).to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The class rust/src/boss.rs could not be found, create it with ftw class or ftw attach"),
+            missing_class_error_message
+        );
+        //
+        let invalid_child_error_message =
+            FtwError::InvalidChild("Sprite3".to_string()).to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The child Sprite3 is not a godot node type"),
+            invalid_child_error_message
+        );
+        //
+        let invalid_root_node_type_error_message =
+            FtwError::InvalidRootNodeType("godot/scenes/Boss.tscn".to_string(), "Boss".to_string())
+                .to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The root node type Boss of the scene godot/scenes/Boss.tscn is not a godot node type"),
+            invalid_root_node_type_error_message
+        );
+        //
+        let missing_node_error_message = FtwError::MissingNode(
+            "Sprite/Anim".to_string(),
+            "res://scenes/Boss.tscn".to_string(),
+        )
+        .to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The node Sprite/Anim could not be found in the scene res://scenes/Boss.tscn"),
+            missing_node_error_message
+        );
+        //
+        let missing_package_file_error_message =
+            FtwError::MissingPackageFile("docs/README.md".to_string()).to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The file docs/README.md to package could not be found"),
+            missing_package_file_error_message
+        );
+        //
+        let invalid_package_file_error_message =
+            FtwError::InvalidPackageFile("../secrets.txt".to_string()).to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The file ../secrets.txt to package is outside of the project"),
+            invalid_package_file_error_message
+        );
+        //
+        let package_entry_collision_error_message =
+            FtwError::PackageEntryCollision("README.md".to_string(), "bin/README.md".to_string())
+                .to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The files README.md and bin/README.md would have the same path inside the archive"),
+            package_entry_collision_error_message
+        );
+        //
+        let gdnlib_not_restored_error_message =
+            FtwError::GdnlibNotRestored("godot/native/game.gdnlib".to_string()).to_message();
+        assert_eq!(
+            format!("{thumbs_down} {styled_error} The original godot/native/game.gdnlib could not be restored after exporting, restore it from version control"),
+            gdnlib_not_restored_error_message
+        );
     }
 }
@@ -0,0 +1,371 @@
+use crate::ftw_build_type::FtwBuildType;
+use crate::ftw_machine_type::FtwMachineType;
+use crate::ftw_manifest::{FtwArtifact, FtwArtifactStatus, FtwManifest};
+use crate::ftw_presets::FtwPresetDrift;
+use crate::ftw_target::FtwTarget;
+use crate::ftw_test_report::FtwTestReport;
+use crate::ftw_upgrade::FtwUpgradeStatus;
+use colored::Colorize;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// The progress of an operation on a project, the command line prints every
+/// event while a [`crate::ftw_project::FtwProject`] hands them to its listener
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FtwEvent {
+    ProjectValidated,
+    FileCreated(String),
+    FileUpdated(String),
+    LibraryBuilt {
+        target: FtwTarget,
+        build_type: FtwBuildType,
+        is_up_to_date: bool,
+    },
+    GameExported {
+        target: FtwTarget,
+        build_type: FtwBuildType,
+        is_up_to_date: bool,
+    },
+    GameStarted {
+        machine_type: FtwMachineType,
+    },
+    GameStopped,
+    TemplateVersion(Option<String>),
+    PathFixed(String),
+    PathMissing(String),
+    FileUpgraded {
+        path: String,
+        status: FtwUpgradeStatus,
+    },
+    PresetAdded(String),
+    PresetDrifted(FtwPresetDrift),
+    ManifestLoaded(FtwManifest),
+    ArtifactVerified {
+        artifact: FtwArtifact,
+        status: FtwArtifactStatus,
+    },
+    OutputLine(String),
+    WatchStarted(Vec<String>),
+    FilesChanged(usize),
+    LibraryRebuilt {
+        target: FtwTarget,
+        build_type: FtwBuildType,
+        result: Result<bool, String>,
+        elapsed: Duration,
+    },
+    TestRunnerSkipped,
+    TestReported(FtwTestReport),
+}
+
+impl Display for FtwEvent {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            FtwEvent::ProjectValidated => write!(f, "Project is valid..."),
+            FtwEvent::FileCreated(path) => write!(f, "{path} has been created..."),
+            FtwEvent::FileUpdated(path) => write!(f, "{path} has been updated..."),
+            FtwEvent::LibraryBuilt {
+                target,
+                build_type,
+                is_up_to_date,
+            } => {
+                let status = if *is_up_to_date {
+                    "is up to date"
+                } else {
+                    "has been installed"
+                };
+                write!(f, "The {build_type} library for {target} {status}...")
+            }
+            FtwEvent::GameExported {
+                target,
+                build_type,
+                is_up_to_date,
+            } => {
+                let status = if *is_up_to_date {
+                    "is up to date"
+                } else {
+                    "has been exported"
+                };
+                write!(f, "The {build_type} game for {target} {status}...")
+            }
+            FtwEvent::GameStarted { machine_type } => {
+                write!(f, "The game has been started as a {machine_type}...")
+            }
+            FtwEvent::GameStopped => write!(f, "The game has been stopped..."),
+            FtwEvent::TemplateVersion(tag) => {
+                let tag = tag.as_deref().unwrap_or("unknown (no .tag)");
+                write!(f, "Template version {tag}")
+            }
+            FtwEvent::PathFixed(path) => write!(f, "[fixed] {path}"),
+            FtwEvent::PathMissing(path) => write!(f, "[missing] {path}"),
+            FtwEvent::FileUpgraded { path, status } => write!(f, "[{status}] {path}"),
+            FtwEvent::PresetAdded(name) => write!(f, "[added] {name}"),
+            FtwEvent::PresetDrifted(FtwPresetDrift {
+                name,
+                key,
+                expected,
+                actual,
+            }) => {
+                let actual = actual.as_deref().unwrap_or("(missing)");
+                write!(
+                    f,
+                    "[drifted] {name} {key} is {actual} instead of {expected}"
+                )
+            }
+            FtwEvent::ManifestLoaded(FtwManifest {
+                kind,
+                target,
+                build_type,
+                crate_name,
+                git_commit,
+                git_dirty,
+                ftw_version,
+                godot_version,
+                files: _,
+            }) => {
+                let kind = kind.to_directory();
+                let git_commit = git_commit.as_deref().unwrap_or("unknown");
+                let dirty = if *git_dirty { " (dirty)" } else { "" };
+                let godot_version = godot_version.as_deref().unwrap_or("-");
+                write!(f, "{kind}/{target} {crate_name} {build_type} commit {git_commit}{dirty} ftw {ftw_version} godot {godot_version}")
+            }
+            FtwEvent::ArtifactVerified {
+                artifact: FtwArtifact { path, size, sha256 },
+                status,
+            } => write!(f, "  [{status}] {path} {size} bytes sha256:{sha256}"),
+            FtwEvent::OutputLine(line) => write!(f, "{line}"),
+            FtwEvent::WatchStarted(paths) => {
+                let paths = paths.join(", ");
+                write!(f, "Watching {paths} for changes...")
+            }
+            FtwEvent::FilesChanged(changes) => {
+                write!(f, "{changes} file(s) changed, rebuilding...")
+            }
+            FtwEvent::LibraryRebuilt {
+                target,
+                build_type,
+                result,
+                elapsed,
+            } => {
+                let elapsed = elapsed.as_secs_f32();
+                let pass = "PASS".green().bold();
+                let fail = "FAIL".red().bold();
+                match result {
+                    Ok(false) => write!(
+                        f,
+                        "{pass} {target} {build_type} library installed ({elapsed:.1}s)"
+                    ),
+                    Ok(true) => write!(
+                        f,
+                        "{pass} {target} {build_type} library up to date ({elapsed:.1}s)"
+                    ),
+                    Err(error) => write!(f, "{fail} {target} {build_type} {error} ({elapsed:.1}s)"),
+                }
+            }
+            FtwEvent::TestRunnerSkipped => write!(
+                f,
+                "No test runner is configured, the godot tests are skipped..."
+            ),
+            FtwEvent::TestReported(report) => write!(f, "{report}"),
+        }
+    }
+}
+
+/// Receives the events of the operations on a project, any `Fn(&FtwEvent)`
+/// closure that can be shared between threads is a listener
+pub trait FtwListener: Send + Sync {
+    fn on_event(&self, event: &FtwEvent);
+}
+
+impl<F: Fn(&FtwEvent) + Send + Sync> FtwListener for F {
+    fn on_event(&self, event: &FtwEvent) {
+        self(event);
+    }
+}
+
+/// The listener of the command line, it prints every event on its own line
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FtwPrinter;
+
+impl FtwListener for FtwPrinter {
+    fn on_event(&self, event: &FtwEvent) {
+        println!("{event}");
+    }
+}
+
+#[cfg(test)]
+mod ftw_event_tests {
+    use super::*;
+    use crate::ftw_manifest::FtwManifestKind;
+    use crate::ftw_test_report::{FtwTestCase, FtwTestStatus, FtwTestSuite};
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(
+            "Project is valid...",
+            FtwEvent::ProjectValidated.to_string()
+        );
+        assert_eq!(
+            "rust/src/lib.rs has been created...",
+            FtwEvent::FileCreated("rust/src/lib.rs".to_string()).to_string()
+        );
+        assert_eq!(
+            "godot/native/game.gdnlib has been updated...",
+            FtwEvent::FileUpdated("godot/native/game.gdnlib".to_string()).to_string()
+        );
+        assert_eq!(
+            "The debug library for x86_64-unknown-linux-gnu is up to date...",
+            FtwEvent::LibraryBuilt {
+                target: FtwTarget::LinuxX86_64,
+                build_type: FtwBuildType::Debug,
+                is_up_to_date: true
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "The release game for x86_64-pc-windows-gnu has been exported...",
+            FtwEvent::GameExported {
+                target: FtwTarget::WindowsX86_64Gnu,
+                build_type: FtwBuildType::Release,
+                is_up_to_date: false
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "The game has been started as a server...",
+            FtwEvent::GameStarted {
+                machine_type: FtwMachineType::Server
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "The game has been stopped...",
+            FtwEvent::GameStopped.to_string()
+        );
+        assert_eq!(
+            "Template version v1.5.0",
+            FtwEvent::TemplateVersion(Some("v1.5.0".to_string())).to_string()
+        );
+        assert_eq!(
+            "Template version unknown (no .tag)",
+            FtwEvent::TemplateVersion(None).to_string()
+        );
+        assert_eq!(
+            "[fixed] lib/x86_64-unknown-linux-gnu",
+            FtwEvent::PathFixed("lib/x86_64-unknown-linux-gnu".to_string()).to_string()
+        );
+        assert_eq!(
+            "[missing] godot/project.godot",
+            FtwEvent::PathMissing("godot/project.godot".to_string()).to_string()
+        );
+        assert_eq!(
+            "[merged] rust/Cargo.toml",
+            FtwEvent::FileUpgraded {
+                path: "rust/Cargo.toml".to_string(),
+                status: FtwUpgradeStatus::Merged
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "[added] Android.aarch64-linux-android.debug",
+            FtwEvent::PresetAdded("Android.aarch64-linux-android.debug".to_string()).to_string()
+        );
+        assert_eq!(
+            "[drifted] Linux/X11.x86_64-unknown-linux-gnu.debug platform is (missing) instead of \"Linux/X11\"",
+            FtwEvent::PresetDrifted(FtwPresetDrift {
+                name: "Linux/X11.x86_64-unknown-linux-gnu.debug".to_string(),
+                key: "platform".to_string(),
+                expected: "\"Linux/X11\"".to_string(),
+                actual: None,
+            })
+            .to_string()
+        );
+        let artifact = FtwArtifact {
+            path: "lib/x86_64-unknown-linux-gnu/libgame.so".to_string(),
+            size: 8,
+            sha256: "d1b2a59f".to_string(),
+        };
+        assert_eq!(
+            "lib/x86_64-unknown-linux-gnu game debug commit unknown (dirty) ftw 0.15.0 godot -",
+            FtwEvent::ManifestLoaded(FtwManifest {
+                kind: FtwManifestKind::Lib,
+                target: "x86_64-unknown-linux-gnu".to_string(),
+                build_type: "debug".to_string(),
+                crate_name: "game".to_string(),
+                git_commit: None,
+                git_dirty: true,
+                ftw_version: "0.15.0".to_string(),
+                godot_version: None,
+                files: vec![artifact.clone()],
+            })
+            .to_string()
+        );
+        assert_eq!(
+            "  [modified] lib/x86_64-unknown-linux-gnu/libgame.so 8 bytes sha256:d1b2a59f",
+            FtwEvent::ArtifactVerified {
+                artifact,
+                status: FtwArtifactStatus::Modified
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "test result: ok",
+            FtwEvent::OutputLine("test result: ok".to_string()).to_string()
+        );
+        assert_eq!(
+            "Watching Cargo.toml, rust/src for changes...",
+            FtwEvent::WatchStarted(vec!["Cargo.toml".to_string(), "rust/src".to_string()])
+                .to_string()
+        );
+        assert_eq!(
+            "2 file(s) changed, rebuilding...",
+            FtwEvent::FilesChanged(2).to_string()
+        );
+        assert_eq!(
+            format!(
+                "{} x86_64-unknown-linux-gnu debug library up to date (1.5s)",
+                "PASS".green().bold()
+            ),
+            FtwEvent::LibraryRebuilt {
+                target: FtwTarget::LinuxX86_64,
+                build_type: FtwBuildType::Debug,
+                result: Ok(true),
+                elapsed: Duration::from_millis(1500)
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "No test runner is configured, the godot tests are skipped...",
+            FtwEvent::TestRunnerSkipped.to_string()
+        );
+        let report = FtwTestReport {
+            suites: vec![FtwTestSuite {
+                name: "rust".to_string(),
+                cases: vec![FtwTestCase {
+                    name: "tests::test_speed".to_string(),
+                    status: FtwTestStatus::Passed,
+                }],
+            }],
+        };
+        assert_eq!(
+            "rust: 1 passed, 0 failed, 0 ignored",
+            FtwEvent::TestReported(report).to_string()
+        );
+    }
+
+    #[test]
+    fn test_on_event() {
+        let events = Mutex::new(vec![]);
+        let listener = |event: &FtwEvent| {
+            if let Ok(mut events) = events.lock() {
+                events.push(event.clone());
+            }
+        };
+        listener.on_event(&FtwEvent::ProjectValidated);
+        assert_eq!(
+            vec![FtwEvent::ProjectValidated],
+            events.into_inner().unwrap_or_default()
+        );
+    }
+}
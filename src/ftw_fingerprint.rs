@@ -0,0 +1,117 @@
+use crate::ftw_build_type::FtwBuildType;
+use crate::ftw_error::FtwError;
+use crate::util;
+use ini::Ini;
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+pub const FINGERPRINT_FILE: &str = ".fingerprint";
+
+/// The fingerprint of the cargo artifact that was last installed into a
+/// `lib/<triple>/` directory
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwFingerprint {
+    pub build_type: FtwBuildType,
+    pub modified: u128,
+    pub hash: String,
+}
+
+impl FtwFingerprint {
+    /// # Errors
+    ///
+    /// Will return `Err` if the file cannot be read
+    pub fn from_path(path: &Path, build_type: FtwBuildType) -> Result<Self, FtwError> {
+        Ok(FtwFingerprint {
+            build_type,
+            modified: FtwFingerprint::get_modified(path)?,
+            hash: util::get_sha256_from_path(path)?,
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the metadata of the file cannot be read
+    pub fn get_modified(path: &Path) -> Result<u128, FtwError> {
+        let modified = fs::metadata(path)?.modified()?;
+        let modified = modified
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| FtwError::PathError)?;
+        Ok(modified.as_nanos())
+    }
+
+    #[must_use]
+    pub fn load(path: &Path) -> Option<Self> {
+        let ini = Ini::load_from_file(path).ok()?;
+        let section = ini.section(Some("fingerprint"))?;
+        Some(FtwFingerprint {
+            build_type: section.get("build-type")?.parse().ok()?,
+            modified: section.get("modified")?.parse().ok()?,
+            hash: section.get("hash")?.to_string(),
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the fingerprint file cannot be written
+    pub fn save(&self, path: &Path) -> Result<(), FtwError> {
+        let mut ini = Ini::new();
+        ini.with_section(Some("fingerprint"))
+            .set("build-type", self.build_type.to_string())
+            .set("modified", self.modified.to_string())
+            .set("hash", self.hash.clone());
+        ini.write_to_file(path)?;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn is_same_artifact(&self, other: &FtwFingerprint) -> bool {
+        self.build_type == other.build_type && self.hash == other.hash
+    }
+}
+
+#[cfg(test)]
+mod ftw_fingerprint_tests {
+    use super::*;
+    use std::env;
+    use std::fs::write;
+
+    #[test]
+    fn test_save_and_load() -> Result<(), FtwError> {
+        let dir = env::temp_dir().join(format!("ftw-fingerprint-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let artifact = dir.join("libgame.so");
+        write(&artifact, b"contents")?;
+        let fingerprint = FtwFingerprint::from_path(&artifact, FtwBuildType::Release)?;
+        assert_eq!(
+            "d1b2a59fbea7e20077af9f91b27e95e865061b270be03ff539ab3b73587882e8",
+            fingerprint.hash
+        );
+        let fingerprint_file = dir.join(FINGERPRINT_FILE);
+        fingerprint.save(&fingerprint_file)?;
+        assert_eq!(
+            Some(fingerprint.clone()),
+            FtwFingerprint::load(&fingerprint_file)
+        );
+        let debug_fingerprint = FtwFingerprint {
+            build_type: FtwBuildType::Debug,
+            ..fingerprint.clone()
+        };
+        assert!(!fingerprint.is_same_artifact(&debug_fingerprint));
+        let touched_fingerprint = FtwFingerprint {
+            modified: 0,
+            ..fingerprint.clone()
+        };
+        assert!(fingerprint.is_same_artifact(&touched_fingerprint));
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_missing() {
+        assert_eq!(
+            None,
+            FtwFingerprint::load(Path::new("no/such/.fingerprint"))
+        );
+    }
+}
@@ -0,0 +1,392 @@
+use crate::ftw_error::FtwError;
+use crate::ftw_target::FtwTarget;
+use crate::traits::{ToCliArg, ToGdnlibFeatures, ToLibExt, ToLibPrefix, ToMessage};
+use crate::util;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, Once};
+use std::time::SystemTime;
+use strum::IntoEnumIterator;
+
+pub const GDNLIB_FILE: &str = "godot/native/game.gdnlib";
+
+const ENTRY_SECTION: &str = "entry";
+const DEPENDENCIES_SECTION: &str = "dependencies";
+const NO_DEPENDENCIES: &str = "[  ]";
+
+static SWITCHED_GDNLIBS: Mutex<Vec<(PathBuf, String, SystemTime)>> = Mutex::new(Vec::new());
+static CTRL_C_HANDLER: Once = Once::new();
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwGdnlibSection {
+    pub name: String,
+    pub lines: Vec<String>,
+}
+
+/// The `game.gdnlib` of a project, every section other than `[entry]` and
+/// `[dependencies]` is written back as it was parsed
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FtwGdnlib {
+    pub sections: Vec<FtwGdnlibSection>,
+}
+
+impl FtwGdnlib {
+    #[must_use]
+    pub fn parse(contents: &str) -> Self {
+        let mut sections: Vec<FtwGdnlibSection> = vec![];
+        for line in contents.lines() {
+            let line = line.trim_end();
+            let header = line
+                .strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+                .filter(|name| !name.contains('='));
+            match (header, sections.last_mut()) {
+                (Some(name), _) => sections.push(FtwGdnlibSection {
+                    name: name.to_string(),
+                    lines: vec![],
+                }),
+                (None, Some(section)) if !line.is_empty() => section.lines.push(line.to_string()),
+                _ => (),
+            }
+        }
+        FtwGdnlib { sections }
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the file cannot be read
+    pub fn load(path: &Path) -> Result<Self, FtwError> {
+        Ok(FtwGdnlib::parse(&fs::read_to_string(path)?))
+    }
+
+    /// Writes the file only if its contents changed, returns `true` if it was written
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the file cannot be written
+    pub fn save(&self, path: &Path) -> Result<bool, FtwError> {
+        let contents = self.to_string();
+        if fs::read_to_string(path).is_ok_and(|old_contents| old_contents == contents) {
+            return Ok(false);
+        }
+        fs::write(path, contents)?;
+        Ok(true)
+    }
+
+    #[must_use]
+    pub fn get_entry(&self, features: &str) -> Option<String> {
+        get_value(self.get_section(ENTRY_SECTION)?, features)
+    }
+
+    #[must_use]
+    pub fn get_dependencies(&self, features: &str) -> Option<String> {
+        get_value(self.get_section(DEPENDENCIES_SECTION)?, features)
+    }
+
+    fn get_section(&self, name: &str) -> Option<&FtwGdnlibSection> {
+        self.sections.iter().find(|section| section.name == name)
+    }
+
+    fn set_section(&mut self, name: &str, lines: Vec<String>) {
+        match self
+            .sections
+            .iter_mut()
+            .find(|section| section.name == name)
+        {
+            Some(section) => section.lines = lines,
+            None => self.sections.push(FtwGdnlibSection {
+                name: name.to_string(),
+                lines,
+            }),
+        }
+    }
+
+    #[must_use]
+    pub fn get_lib_path(target: FtwTarget, crate_name: &str) -> String {
+        let target_cli_arg = target.to_cli_arg();
+        let target_lib_prefix = target.to_lib_prefix();
+        let target_lib_ext = target.to_lib_ext();
+        format!(
+            "\"res://../lib/{target_cli_arg}/{target_lib_prefix}{crate_name}.{target_lib_ext}\""
+        )
+    }
+
+    /// Generates the `[entry]` and `[dependencies]` of every target, the
+    /// Windows entries keep pointing to gnu libraries if they already did
+    /// and the entries added by the user are kept, returns `true` if an entry
+    /// or a dependency changed
+    pub fn sync(&mut self, crate_name: &str) -> bool {
+        let original = self.clone();
+        let mut entries: Vec<(String, String)> = vec![];
+        for target in FtwTarget::iter() {
+            let features = target.to_gdnlib_features();
+            if entries.iter().any(|(key, _)| key == &features) {
+                continue;
+            }
+            let is_gnu = self
+                .get_entry(&features)
+                .is_some_and(|entry| entry.contains("-windows-gnu/"));
+            let target = match target {
+                FtwTarget::WindowsX86Gnu | FtwTarget::WindowsX86Msvc if is_gnu => {
+                    FtwTarget::WindowsX86Gnu
+                }
+                FtwTarget::WindowsX86Gnu | FtwTarget::WindowsX86Msvc => FtwTarget::WindowsX86Msvc,
+                FtwTarget::WindowsX86_64Gnu | FtwTarget::WindowsX86_64Msvc if is_gnu => {
+                    FtwTarget::WindowsX86_64Gnu
+                }
+                FtwTarget::WindowsX86_64Gnu | FtwTarget::WindowsX86_64Msvc => {
+                    FtwTarget::WindowsX86_64Msvc
+                }
+                target => target,
+            };
+            entries.push((features, FtwGdnlib::get_lib_path(target, crate_name)));
+        }
+        let user_entries: Vec<(String, String)> = self
+            .get_section(ENTRY_SECTION)
+            .map(get_pairs)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(key, _)| !entries.iter().any(|(features, _)| features == key))
+            .collect();
+        entries.extend(user_entries);
+        let dependencies: Vec<(String, String)> = entries
+            .iter()
+            .map(|(features, _)| {
+                let dependencies = self
+                    .get_dependencies(features)
+                    .unwrap_or_else(|| NO_DEPENDENCIES.to_string());
+                (features.clone(), dependencies)
+            })
+            .collect();
+        self.set_section(ENTRY_SECTION, to_lines(&entries));
+        self.set_section(DEPENDENCIES_SECTION, to_lines(&dependencies));
+        *self != original
+    }
+
+    /// Points the entry of the platform of `target` to the library of `target`
+    pub fn switch(&mut self, target: FtwTarget, crate_name: &str) {
+        let features = target.to_gdnlib_features();
+        let lib_path = FtwGdnlib::get_lib_path(target, crate_name);
+        let mut entries = self
+            .get_section(ENTRY_SECTION)
+            .map(get_pairs)
+            .unwrap_or_default();
+        match entries.iter_mut().find(|(key, _)| key == &features) {
+            Some((_, entry)) => *entry = lib_path,
+            None => entries.push((features, lib_path)),
+        }
+        self.set_section(ENTRY_SECTION, to_lines(&entries));
+    }
+}
+
+impl std::fmt::Display for FtwGdnlib {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let sections: Vec<String> = self
+            .sections
+            .iter()
+            .map(|FtwGdnlibSection { name, lines }| {
+                let lines: String = lines.iter().map(|line| format!("{line}\n")).collect();
+                format!("[{name}]\n\n{lines}")
+            })
+            .collect();
+        write!(f, "{}", sections.join("\n"))
+    }
+}
+
+/// Switches the entries of `game.gdnlib` to the library of a target while
+/// exporting and restores the original file (and its modification time)
+/// once restored, dropped or when Ctrl-C is pressed
+#[derive(Debug)]
+pub struct FtwGdnlibSwitch {
+    path: PathBuf,
+    original: Option<(String, SystemTime)>,
+}
+
+impl FtwGdnlibSwitch {
+    /// Nothing is switched if the file does not exist
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the file cannot be read or written
+    pub fn new(path: &Path, target: FtwTarget, crate_name: &str) -> Result<Self, FtwError> {
+        if !path.exists() {
+            return Ok(FtwGdnlibSwitch {
+                path: path.to_path_buf(),
+                original: None,
+            });
+        }
+        let contents = fs::read_to_string(path)?;
+        let modified = fs::metadata(path)?.modified()?;
+        let mut gdnlib = FtwGdnlib::parse(&contents);
+        gdnlib.switch(target, crate_name);
+        let is_switched = gdnlib.to_string() != FtwGdnlib::parse(&contents).to_string();
+        let original = if is_switched {
+            CTRL_C_HANDLER.call_once(|| util::on_ctrl_c(FtwGdnlibSwitch::restore_all));
+            if let Ok(mut switched_gdnlibs) = SWITCHED_GDNLIBS.lock() {
+                switched_gdnlibs.push((path.to_path_buf(), contents.clone(), modified));
+            }
+            gdnlib.save(path)?;
+            Some((contents, modified))
+        } else {
+            None
+        };
+        Ok(FtwGdnlibSwitch {
+            path: path.to_path_buf(),
+            original,
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the original file cannot be written back
+    pub fn restore(&mut self) -> Result<(), FtwError> {
+        match self.original.take() {
+            Some((contents, modified)) => {
+                if let Ok(mut switched_gdnlibs) = SWITCHED_GDNLIBS.lock() {
+                    switched_gdnlibs.retain(|(path, _, _)| path != &self.path);
+                }
+                FtwGdnlibSwitch::write_original(&self.path, &contents, modified)
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn write_original(path: &Path, contents: &str, modified: SystemTime) -> Result<(), FtwError> {
+        fs::write(path, contents)
+            .and_then(|()| File::options().write(true).open(path))
+            .and_then(|file| file.set_modified(modified))
+            .map_err(|_| FtwError::GdnlibNotRestored(path.display().to_string()))
+    }
+
+    fn restore_all() {
+        if let Ok(mut switched_gdnlibs) = SWITCHED_GDNLIBS.lock() {
+            for (path, contents, modified) in switched_gdnlibs.drain(..) {
+                if let Err(error) = FtwGdnlibSwitch::write_original(&path, &contents, modified) {
+                    eprintln!("{}", error.to_message());
+                }
+            }
+        }
+    }
+}
+
+impl Drop for FtwGdnlibSwitch {
+    fn drop(&mut self) {
+        if let Err(error) = self.restore() {
+            eprintln!("{}", error.to_message());
+        }
+    }
+}
+
+fn get_pairs(section: &FtwGdnlibSection) -> Vec<(String, String)> {
+    section
+        .lines
+        .iter()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+fn get_value(section: &FtwGdnlibSection, key: &str) -> Option<String> {
+    get_pairs(section)
+        .into_iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, value)| value)
+}
+
+fn to_lines(pairs: &[(String, String)]) -> Vec<String> {
+    pairs
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod ftw_gdnlib_tests {
+    use super::*;
+    use std::env;
+
+    const GDNLIB: &str = r#"[general]
+
+singleton=false
+load_once=true
+symbol_prefix="godot_"
+reloadable=true
+
+[entry]
+
+Windows.64="res://../lib/x86_64-pc-windows-gnu/game.dll"
+Server.64="res://../lib/x86_64-unknown-linux-gnu/libgame.so"
+
+[dependencies]
+
+X11.64=[ "res://../lib/libextra.so" ]
+"#;
+
+    #[test]
+    fn test_sync() {
+        let mut gdnlib = FtwGdnlib::parse(GDNLIB);
+        assert!(gdnlib.sync("game"));
+        assert_eq!(
+            Some("\"res://../lib/x86_64-pc-windows-gnu/game.dll\"".to_string()),
+            gdnlib.get_entry("Windows.64")
+        );
+        assert_eq!(
+            Some("\"res://../lib/i686-pc-windows-msvc/game.dll\"".to_string()),
+            gdnlib.get_entry("Windows.32")
+        );
+        assert_eq!(
+            Some("\"res://../lib/aarch64-apple-darwin/libgame.dylib\"".to_string()),
+            gdnlib.get_entry("OSX.arm64")
+        );
+        assert_eq!(
+            Some("\"res://../lib/x86_64-unknown-linux-gnu/libgame.so\"".to_string()),
+            gdnlib.get_entry("Server.64")
+        );
+        assert_eq!(
+            Some("[ \"res://../lib/libextra.so\" ]".to_string()),
+            gdnlib.get_dependencies("X11.64")
+        );
+        assert_eq!(
+            Some("[  ]".to_string()),
+            gdnlib.get_dependencies("iOS.arm64")
+        );
+        let contents = gdnlib.to_string();
+        assert!(contents.starts_with("[general]\n\nsingleton=false\n"));
+        assert_eq!(gdnlib, FtwGdnlib::parse(&contents));
+        assert!(!gdnlib.sync("game"));
+        let mut gdnlib = FtwGdnlib::parse(&contents.replace('\n', "\r\n"));
+        assert!(!gdnlib.sync("game"));
+    }
+
+    #[test]
+    fn test_switch() -> Result<(), FtwError> {
+        let dir = env::temp_dir().join(format!("ftw-gdnlib-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("game.gdnlib");
+        fs::write(&path, GDNLIB)?;
+        let modified = fs::metadata(&path)?.modified()?;
+        {
+            let _switch = FtwGdnlibSwitch::new(&path, FtwTarget::WindowsX86_64Msvc, "game")?;
+            let gdnlib = FtwGdnlib::load(&path)?;
+            assert_eq!(
+                Some("\"res://../lib/x86_64-pc-windows-msvc/game.dll\"".to_string()),
+                gdnlib.get_entry("Windows.64")
+            );
+        }
+        assert_eq!(GDNLIB, fs::read_to_string(&path)?);
+        assert_eq!(modified, fs::metadata(&path)?.modified()?);
+        let mut switch = FtwGdnlibSwitch::new(&path, FtwTarget::WindowsX86_64Msvc, "game")?;
+        assert!(SWITCHED_GDNLIBS
+            .lock()
+            .is_ok_and(|switched_gdnlibs| switched_gdnlibs.iter().any(|(p, _, _)| p == &path)));
+        fs::remove_dir_all(&dir)?;
+        assert!(matches!(
+            switch.restore(),
+            Err(FtwError::GdnlibNotRestored(restored_path)) if restored_path == path.display().to_string()
+        ));
+        assert!(SWITCHED_GDNLIBS
+            .lock()
+            .is_ok_and(|switched_gdnlibs| switched_gdnlibs.iter().all(|(p, _, _)| p != &path)));
+        Ok(())
+    }
+}
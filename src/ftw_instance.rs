@@ -0,0 +1,216 @@
+use crate::ftw_error::FtwError;
+use crate::ftw_machine_type::FtwMachineType;
+use crate::util;
+use colored::{Color, ColoredString, Colorize};
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Mutex, Once};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const CLIENT_COLORS: [Color; 5] = [
+    Color::Green,
+    Color::Yellow,
+    Color::Magenta,
+    Color::Blue,
+    Color::Red,
+];
+
+static RUNNING_INSTANCES: Mutex<Vec<Child>> = Mutex::new(Vec::new());
+static CTRL_C_HANDLER: Once = Once::new();
+
+/// One of the godot processes started by `ftw run --server --clients <n>`,
+/// the server is instance 0 and the clients are numbered from 1
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FtwInstance {
+    pub machine_type: FtwMachineType,
+    pub index: usize,
+}
+
+impl FtwInstance {
+    #[must_use]
+    pub fn get_instances(server: bool, clients: usize) -> Vec<FtwInstance> {
+        let server = server.then_some(FtwInstance {
+            machine_type: FtwMachineType::Server,
+            index: 0,
+        });
+        let clients = (1..=clients).map(|index| FtwInstance {
+            machine_type: FtwMachineType::Desktop,
+            index,
+        });
+        server.into_iter().chain(clients).collect()
+    }
+
+    #[must_use]
+    pub fn get_name(&self) -> String {
+        match self.machine_type {
+            FtwMachineType::Server => "server".to_string(),
+            FtwMachineType::Desktop => format!("client {}", self.index),
+        }
+    }
+
+    fn get_role(&self) -> &str {
+        match self.machine_type {
+            FtwMachineType::Server => "server",
+            FtwMachineType::Desktop => "client",
+        }
+    }
+
+    #[must_use]
+    pub fn get_color(&self) -> Color {
+        match self.machine_type {
+            FtwMachineType::Server => Color::Cyan,
+            FtwMachineType::Desktop => {
+                CLIENT_COLORS[self.index.saturating_sub(1) % CLIENT_COLORS.len()]
+            }
+        }
+    }
+
+    fn get_prefix(&self) -> ColoredString {
+        format!("[{}]", self.get_name())
+            .color(self.get_color())
+            .bold()
+    }
+
+    /// Replaces `{instance}` with the number of the instance and `{role}`
+    /// with either `server` or `client` inside the arguments
+    #[must_use]
+    pub fn get_args(&self, godot_args: &[String]) -> Vec<String> {
+        godot_args
+            .iter()
+            .map(|arg| {
+                arg.replace("{instance}", &self.index.to_string())
+                    .replace("{role}", self.get_role())
+            })
+            .collect()
+    }
+
+    /// Starts every instance with its output prefixed by the name of the
+    /// instance and waits until all of them exited, every instance still
+    /// running is killed when Ctrl-C is pressed
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an instance cannot be started, the instances that were already started are killed
+    pub fn run_all(instances: Vec<(FtwInstance, Command)>) -> Result<(), FtwError> {
+        CTRL_C_HANDLER.call_once(|| util::on_ctrl_c(FtwInstance::kill_all));
+        let mut forwarders = vec![];
+        for (instance, mut command) in instances {
+            let mut child = match command
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+            {
+                Ok(child) => child,
+                Err(error) => {
+                    FtwInstance::kill_all();
+                    return Err(error.into());
+                }
+            };
+            let prefix = instance.get_prefix();
+            if let Some(stdout) = child.stdout.take() {
+                forwarders.push(FtwInstance::forward(stdout, prefix.clone(), false));
+            }
+            if let Some(stderr) = child.stderr.take() {
+                forwarders.push(FtwInstance::forward(stderr, prefix, true));
+            }
+            if let Ok(mut running_instances) = RUNNING_INSTANCES.lock() {
+                running_instances.push(child);
+            }
+        }
+        loop {
+            if let Ok(mut running_instances) = RUNNING_INSTANCES.lock() {
+                running_instances.retain_mut(|child| matches!(child.try_wait(), Ok(None)));
+                if running_instances.is_empty() {
+                    break;
+                }
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        for forwarder in forwarders {
+            drop(forwarder.join());
+        }
+        Ok(())
+    }
+
+    fn forward(
+        output: impl Read + Send + 'static,
+        prefix: ColoredString,
+        is_stderr: bool,
+    ) -> JoinHandle<()> {
+        thread::spawn(move || {
+            for line in BufReader::new(output).split(b'\n').map_while(Result::ok) {
+                let line = String::from_utf8_lossy(&line);
+                let line = line.trim_end_matches('\r');
+                if is_stderr {
+                    eprintln!("{prefix} {line}");
+                } else {
+                    println!("{prefix} {line}");
+                }
+            }
+        })
+    }
+
+    fn kill_all() {
+        if let Ok(mut running_instances) = RUNNING_INSTANCES.lock() {
+            for child in running_instances.iter_mut() {
+                drop(child.kill());
+                drop(child.wait());
+            }
+            running_instances.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod ftw_instance_tests {
+    use super::*;
+    use command_macros::cmd;
+
+    #[test]
+    fn test_get_instances() {
+        let instances = FtwInstance::get_instances(true, 2);
+        let names: Vec<String> = instances.iter().map(FtwInstance::get_name).collect();
+        assert_eq!(vec!["server", "client 1", "client 2"], names);
+        assert_eq!(Color::Cyan, instances[0].get_color());
+        assert_ne!(instances[1].get_color(), instances[2].get_color());
+        assert_eq!(
+            vec![FtwInstance {
+                machine_type: FtwMachineType::Desktop,
+                index: 1
+            }],
+            FtwInstance::get_instances(false, 1)
+        );
+    }
+
+    #[test]
+    fn test_get_args() {
+        let godot_args = vec![
+            "--".to_string(),
+            "--player-id".to_string(),
+            "{instance}".to_string(),
+            "--{role}".to_string(),
+        ];
+        let instances = FtwInstance::get_instances(true, 1);
+        assert_eq!(
+            vec!["--", "--player-id", "0", "--server"],
+            instances[0].get_args(&godot_args)
+        );
+        assert_eq!(
+            vec!["--", "--player-id", "1", "--client"],
+            instances[1].get_args(&godot_args)
+        );
+    }
+
+    #[test]
+    fn test_run_all() -> Result<(), FtwError> {
+        let instances = FtwInstance::get_instances(true, 1)
+            .into_iter()
+            .map(|instance| (instance, cmd!(ls("-al"))))
+            .collect();
+        FtwInstance::run_all(instances)?;
+        let instances = vec![(FtwInstance::get_instances(true, 0)[0], cmd!(gogogo))];
+        assert!(FtwInstance::run_all(instances).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+use crate::ftw_build_type::FtwBuildType;
+use crate::ftw_error::FtwError;
+use crate::ftw_presets::{FtwPreset, FtwPresets};
+use crate::ftw_target::FtwTarget;
+use crate::traits::Runner;
+use command_macros::cmd;
+use std::env;
+use std::path::Path;
+use strum::IntoEnumIterator;
+
+pub const KEYSTORE_PASSWORD_ENV: &str = "GODOT_ANDROID_KEYSTORE_RELEASE_PASSWORD";
+pub const DEBUG_KEYSTORE_ALIAS: &str = "androiddebugkey";
+pub const DEBUG_KEYSTORE_PASSWORD: &str = "android";
+
+/// The keystore used to sign the Android games of a build type, it is
+/// created at the project root and referenced from `godot/` by the presets
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwKeystore {
+    pub build_type: FtwBuildType,
+    pub alias: String,
+}
+
+#[rustfmt::skip::macros(cmd)]
+impl FtwKeystore {
+    #[must_use]
+    pub fn new(build_type: FtwBuildType, crate_name: &str) -> Self {
+        let alias = match build_type {
+            FtwBuildType::Debug => DEBUG_KEYSTORE_ALIAS.to_string(),
+            FtwBuildType::Release => crate_name.to_string(),
+        };
+        FtwKeystore { build_type, alias }
+    }
+
+    #[must_use]
+    pub fn get_path(&self) -> String {
+        let build_type = self.build_type;
+        format!("{build_type}.keystore")
+    }
+
+    /// Generates the keystore with `keytool` if it does not exist yet, the
+    /// release keystore password is read from the
+    /// `GODOT_ANDROID_KEYSTORE_RELEASE_PASSWORD` environment variable
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the release keystore password is not set or the keystore could not be generated
+    pub fn create(&self, root: &Path) -> Result<bool, FtwError> {
+        let path = root.join(self.get_path());
+        if path.exists() {
+            return Ok(false);
+        }
+        let alias = &self.alias;
+        let password_args = match self.build_type {
+            FtwBuildType::Debug => vec![
+                "-storepass".to_string(),
+                DEBUG_KEYSTORE_PASSWORD.to_string(),
+                "-keypass".to_string(),
+                DEBUG_KEYSTORE_PASSWORD.to_string(),
+            ],
+            FtwBuildType::Release => {
+                FtwKeystore::get_release_password()?;
+                vec![
+                    "-storepass:env".to_string(),
+                    KEYSTORE_PASSWORD_ENV.to_string(),
+                    "-keypass:env".to_string(),
+                    KEYSTORE_PASSWORD_ENV.to_string(),
+                ]
+            }
+        };
+        let dname = format!("CN={alias}");
+        cmd!(keytool ("-genkeypair") ("-noprompt") ("-keyalg") ("RSA") ("-keysize") ("2048") ("-validity") ("10000") ("-alias") (alias) ("-dname") (dname) ("-keystore") (path) [password_args]).run()?;
+        if path.exists() {
+            Ok(true)
+        } else {
+            Err(FtwError::MissingKeystore(self.build_type))
+        }
+    }
+
+    fn get_release_password() -> Result<String, FtwError> {
+        env::var(KEYSTORE_PASSWORD_ENV)
+            .ok()
+            .filter(|password| !password.is_empty())
+            .ok_or(FtwError::MissingKeystorePassword)
+    }
+
+    /// The keystore options of the Android presets, the release password is
+    /// left empty since Godot reads it from `GODOT_ANDROID_KEYSTORE_RELEASE_PASSWORD`
+    #[must_use]
+    pub fn get_preset_options(&self) -> Vec<(String, String)> {
+        let build_type = self.build_type;
+        let path = self.get_path();
+        let password = match build_type {
+            FtwBuildType::Debug => DEBUG_KEYSTORE_PASSWORD,
+            FtwBuildType::Release => "",
+        };
+        vec![
+            (format!("keystore/{build_type}"), format!("\"../{path}\"")),
+            (
+                format!("keystore/{build_type}_user"),
+                format!("\"{}\"", self.alias),
+            ),
+            (
+                format!("keystore/{build_type}_password"),
+                format!("\"{password}\""),
+            ),
+        ]
+    }
+
+    /// Writes the keystore options into the Android presets of the build type,
+    /// returns the names of the presets that were updated
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the presets cannot be parsed again
+    pub fn configure(&self, presets: &mut FtwPresets) -> Result<Vec<String>, FtwError> {
+        let mut names = vec![];
+        for target in FtwTarget::iter().filter(|target| target.is_android()) {
+            let name = FtwPreset::get_name(target, self.build_type);
+            for (key, value) in self.get_preset_options() {
+                if !presets.set_option(&name, &key, &value)? {
+                    break;
+                }
+            }
+            if presets.get(&name).is_some() {
+                names.push(name);
+            }
+        }
+        Ok(names)
+    }
+
+    /// Checks that the preset of `target` points to an existing keystore of the
+    /// project at `root` and that a password is available
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the keystore is not configured, is missing or has no password
+    pub fn check(
+        root: &Path,
+        target: FtwTarget,
+        build_type: FtwBuildType,
+        presets: &FtwPresets,
+    ) -> Result<(), FtwError> {
+        let name = FtwPreset::get_name(target, build_type);
+        let option = |key: String| {
+            presets
+                .get(&name)
+                .and_then(|preset| preset.get_option(&key))
+                .map(|value| value.trim_matches('"').to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let keystore = option(format!("keystore/{build_type}"))
+            .ok_or(FtwError::MissingKeystore(build_type))?;
+        let keystore_path = root.join("godot").join(keystore);
+        if !keystore_path.is_file() {
+            return Err(FtwError::MissingKeystore(build_type));
+        }
+        if option(format!("keystore/{build_type}_password")).is_none() {
+            FtwKeystore::get_release_password()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod ftw_keystore_tests {
+    use super::*;
+
+    #[test]
+    fn test_get_preset_options() {
+        let keystore = FtwKeystore::new(FtwBuildType::Debug, "game");
+        assert_eq!("debug.keystore", keystore.get_path());
+        assert_eq!(
+            vec![
+                (
+                    "keystore/debug".to_string(),
+                    "\"../debug.keystore\"".to_string()
+                ),
+                (
+                    "keystore/debug_user".to_string(),
+                    "\"androiddebugkey\"".to_string()
+                ),
+                (
+                    "keystore/debug_password".to_string(),
+                    "\"android\"".to_string()
+                ),
+            ],
+            keystore.get_preset_options()
+        );
+        let keystore = FtwKeystore::new(FtwBuildType::Release, "game");
+        assert_eq!(
+            vec![
+                (
+                    "keystore/release".to_string(),
+                    "\"../release.keystore\"".to_string()
+                ),
+                ("keystore/release_user".to_string(), "\"game\"".to_string()),
+                ("keystore/release_password".to_string(), "\"\"".to_string()),
+            ],
+            keystore.get_preset_options()
+        );
+    }
+
+    #[test]
+    fn test_configure_and_check() -> Result<(), FtwError> {
+        let mut presets = FtwPresets::default();
+        presets.sync("game");
+        let target = FtwTarget::AndroidLinuxAarch64;
+        assert!(matches!(
+            FtwKeystore::check(Path::new("."), target, FtwBuildType::Debug, &presets),
+            Err(FtwError::MissingKeystore(FtwBuildType::Debug))
+        ));
+        let keystore = FtwKeystore::new(FtwBuildType::Debug, "game");
+        let names = keystore.configure(&mut presets)?;
+        assert_eq!(4, names.len());
+        assert!(names.contains(&"Android.aarch64-linux-android.debug".to_string()));
+        let preset = presets
+            .get("Android.aarch64-linux-android.debug")
+            .ok_or(FtwError::PathError)?;
+        assert_eq!(
+            Some("\"androiddebugkey\""),
+            preset.get_option("keystore/debug_user")
+        );
+        assert_eq!(Some("true"), preset.get_option("architectures/arm64-v8a"));
+        assert!(matches!(
+            FtwKeystore::check(Path::new("."), target, FtwBuildType::Debug, &presets),
+            Err(FtwError::MissingKeystore(FtwBuildType::Debug))
+        ));
+        Ok(())
+    }
+}
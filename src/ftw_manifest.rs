@@ -0,0 +1,352 @@
+use crate::ftw_build_type::FtwBuildType;
+use crate::ftw_error::FtwError;
+use crate::ftw_target::FtwTarget;
+use crate::traits::{ToAppExt, ToCliArg, ToLibExt, ToLibPrefix};
+use crate::util;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, read_dir, File};
+use std::path::{Path, PathBuf};
+
+const MANIFEST_EXT: &str = ".manifest.json";
+const ARTIFACT_EXTS: [&str; 7] = [".so", ".dll", ".dylib", ".apk", ".exe", ".pck", ".zip"];
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FtwManifestKind {
+    Lib,
+    Game,
+}
+
+impl FtwManifestKind {
+    #[must_use]
+    pub fn to_directory(self) -> &'static str {
+        match self {
+            FtwManifestKind::Lib => "lib",
+            FtwManifestKind::Game => "bin",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FtwArtifact {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FtwArtifactStatus {
+    Ok,
+    Modified,
+    Missing,
+}
+
+impl Display for FtwArtifactStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let status = match self {
+            FtwArtifactStatus::Ok => "ok",
+            FtwArtifactStatus::Modified => "modified",
+            FtwArtifactStatus::Missing => "missing",
+        };
+        write!(f, "{status}")
+    }
+}
+
+/// A record of what produced the artifacts of a build or an export
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FtwManifest {
+    pub kind: FtwManifestKind,
+    pub target: String,
+    pub build_type: String,
+    pub crate_name: String,
+    pub git_commit: Option<String>,
+    pub git_dirty: bool,
+    pub ftw_version: String,
+    pub godot_version: Option<String>,
+    pub files: Vec<FtwArtifact>,
+}
+
+impl FtwManifest {
+    /// Creates the manifest of the files that were produced for `target` and
+    /// `build_type` inside `lib/<triple>/` or `bin/<triple>/` of the project
+    /// at `root`, the paths of the files are relative to `root`, the library
+    /// next to an exported game is shared by both build types so it is only
+    /// part of the library manifest
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the crate name cannot be read or the artifacts cannot be hashed
+    pub fn create(
+        root: &Path,
+        kind: FtwManifestKind,
+        target: FtwTarget,
+        build_type: FtwBuildType,
+        godot_version: Option<String>,
+    ) -> Result<Self, FtwError> {
+        let crate_name = util::get_crate_name_from_path(&root.join("rust"))?;
+        let target_cli_arg = target.to_cli_arg();
+        let directory = kind.to_directory();
+        let artifact_dir = format!("{directory}/{target_cli_arg}");
+        let target_lib_prefix = target.to_lib_prefix();
+        let target_lib_ext = target.to_lib_ext();
+        let lib_file = format!("{target_lib_prefix}{crate_name}.{target_lib_ext}");
+        let game_file_prefix = format!("{crate_name}.{build_type}.{target_cli_arg}");
+        let game_app_file = format!("{game_file_prefix}{}", target.to_app_ext());
+        let mut file_names: Vec<String> = read_dir(root.join(&artifact_dir))?
+            .filter_map(Result::ok)
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|file_name| {
+                let is_artifact = ARTIFACT_EXTS.iter().any(|ext| file_name.ends_with(ext));
+                let is_lib_file = file_name == &lib_file && kind == FtwManifestKind::Lib;
+                let is_game_file =
+                    file_name.starts_with(&game_file_prefix) && kind == FtwManifestKind::Game;
+                is_lib_file || (is_game_file && (is_artifact || file_name == &game_app_file))
+            })
+            .collect();
+        file_names.sort();
+        let files = file_names
+            .iter()
+            .map(|file_name| FtwArtifact::from_path(root, &format!("{artifact_dir}/{file_name}")))
+            .collect::<Result<Vec<FtwArtifact>, FtwError>>()?;
+        Ok(FtwManifest {
+            kind,
+            target: target_cli_arg,
+            build_type: build_type.to_string(),
+            crate_name,
+            git_commit: util::get_git_commit(root),
+            git_dirty: util::is_git_dirty(root),
+            ftw_version: env!("CARGO_PKG_VERSION").to_string(),
+            godot_version,
+            files,
+        })
+    }
+
+    /// The path of the manifest, `lib/<triple>/` holds a single library for
+    /// both build types so there is a single library manifest per target
+    /// (recording the build type of the last build) while every build type of
+    /// an exported game has its own manifest
+    #[must_use]
+    pub fn get_path(&self) -> PathBuf {
+        let directory = self.kind.to_directory();
+        let target = &self.target;
+        let crate_name = &self.crate_name;
+        let build_type = &self.build_type;
+        match self.kind {
+            FtwManifestKind::Lib => {
+                PathBuf::from(format!("{directory}/{target}/{crate_name}{MANIFEST_EXT}"))
+            }
+            FtwManifestKind::Game => PathBuf::from(format!(
+                "{directory}/{target}/{crate_name}.{build_type}{MANIFEST_EXT}"
+            )),
+        }
+    }
+
+    /// Writes the manifest inside the project at `root`, returns its path
+    /// relative to `root`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the manifest cannot be written
+    pub fn save(&self, root: &Path) -> Result<PathBuf, FtwError> {
+        let path = self.get_path();
+        let file = File::create(root.join(&path))?;
+        serde_json::to_writer_pretty(file, self)?;
+        Ok(path)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the manifest cannot be read or parsed
+    pub fn load(path: &Path) -> Result<Self, FtwError> {
+        let file = File::open(path)?;
+        Ok(serde_json::from_reader(file)?)
+    }
+
+    /// Loads every manifest inside `lib/` and `bin/` of the project at `root`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if a manifest cannot be read or parsed
+    pub fn load_all(root: &Path) -> Result<Vec<FtwManifest>, FtwError> {
+        let mut paths = vec![];
+        for kind in [FtwManifestKind::Lib, FtwManifestKind::Game] {
+            let directory = root.join(kind.to_directory());
+            if !directory.is_dir() {
+                continue;
+            }
+            for target_dir in read_dir(&directory)? {
+                let target_dir = target_dir?.path();
+                if !target_dir.is_dir() {
+                    continue;
+                }
+                for entry in read_dir(target_dir)? {
+                    let path = entry?.path();
+                    let is_manifest = path
+                        .file_name()
+                        .and_then(|file_name| file_name.to_str())
+                        .is_some_and(|file_name| file_name.ends_with(MANIFEST_EXT));
+                    if is_manifest {
+                        paths.push(path);
+                    }
+                }
+            }
+        }
+        paths.sort();
+        paths.iter().map(|path| FtwManifest::load(path)).collect()
+    }
+
+    /// Verifies the files of the manifest inside the project at `root`
+    #[must_use]
+    pub fn verify(&self, root: &Path) -> Vec<(&FtwArtifact, FtwArtifactStatus)> {
+        self.files
+            .iter()
+            .map(|artifact| (artifact, artifact.verify(root)))
+            .collect()
+    }
+}
+
+impl FtwArtifact {
+    /// The artifact of the file at `path` relative to `root`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the file cannot be read
+    pub fn from_path(root: &Path, path: &str) -> Result<Self, FtwError> {
+        let file_path = root.join(path);
+        Ok(FtwArtifact {
+            path: path.to_string(),
+            size: fs::metadata(&file_path)?.len(),
+            sha256: util::get_sha256_from_path(&file_path)?,
+        })
+    }
+
+    #[must_use]
+    pub fn verify(&self, root: &Path) -> FtwArtifactStatus {
+        match FtwArtifact::from_path(root, &self.path) {
+            Ok(artifact) if &artifact == self => FtwArtifactStatus::Ok,
+            Ok(_) => FtwArtifactStatus::Modified,
+            Err(_) => FtwArtifactStatus::Missing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod ftw_manifest_tests {
+    use super::*;
+    use crate::test_util::create_project_skeleton;
+    use std::env;
+
+    #[test]
+    fn test_artifact_verify() -> Result<(), FtwError> {
+        let dir = env::temp_dir().join(format!("ftw-manifest-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("lib/libgame.so");
+        fs::create_dir_all(dir.join("lib"))?;
+        fs::write(&path, b"contents")?;
+        let artifact = FtwArtifact::from_path(&dir, "lib/libgame.so")?;
+        assert_eq!("lib/libgame.so", artifact.path);
+        assert_eq!(8, artifact.size);
+        assert_eq!(FtwArtifactStatus::Ok, artifact.verify(&dir));
+        fs::write(&path, b"modified")?;
+        assert_eq!(FtwArtifactStatus::Modified, artifact.verify(&dir));
+        fs::remove_dir_all(&dir)?;
+        assert_eq!(FtwArtifactStatus::Missing, artifact.verify(&dir));
+        Ok(())
+    }
+
+    #[test]
+    fn test_game_manifests() -> Result<(), FtwError> {
+        let root = env::temp_dir().join(format!("ftw-manifest-game-{}", std::process::id()));
+        create_project_skeleton(&root)?;
+        let target = FtwTarget::LinuxX86_64;
+        let bin_dir = root.join("bin").join(target.to_cli_arg());
+        let export = |build_type: FtwBuildType| -> Result<FtwManifest, FtwError> {
+            fs::write(bin_dir.join("libgame.so"), build_type.to_string())?;
+            fs::write(
+                bin_dir.join(format!("game.{build_type}.x86_64-unknown-linux-gnu.x86_64")),
+                build_type.to_string(),
+            )?;
+            fs::write(
+                bin_dir.join(format!("game.{build_type}.x86_64-unknown-linux-gnu.pck")),
+                build_type.to_string(),
+            )?;
+            let manifest =
+                FtwManifest::create(&root, FtwManifestKind::Game, target, build_type, None)?;
+            manifest.save(&root)?;
+            Ok(manifest)
+        };
+        let debug_manifest = export(FtwBuildType::Debug)?;
+        let release_manifest = export(FtwBuildType::Release)?;
+        assert_eq!(
+            vec![
+                "bin/x86_64-unknown-linux-gnu/game.debug.x86_64-unknown-linux-gnu.pck",
+                "bin/x86_64-unknown-linux-gnu/game.debug.x86_64-unknown-linux-gnu.x86_64",
+            ],
+            debug_manifest
+                .files
+                .iter()
+                .map(|artifact| artifact.path.as_str())
+                .collect::<Vec<&str>>()
+        );
+        let manifests = FtwManifest::load_all(&root)?;
+        assert_eq!(vec![debug_manifest, release_manifest], manifests);
+        for manifest in &manifests {
+            assert!(manifest
+                .verify(&root)
+                .iter()
+                .all(|(_, status)| *status == FtwArtifactStatus::Ok));
+        }
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_manifest_json() -> Result<(), FtwError> {
+        let manifest = FtwManifest {
+            kind: FtwManifestKind::Game,
+            target: "x86_64-unknown-linux-gnu".to_string(),
+            build_type: "release".to_string(),
+            crate_name: "game".to_string(),
+            git_commit: Some("abc123".to_string()),
+            git_dirty: true,
+            ftw_version: "0.15.0".to_string(),
+            godot_version: None,
+            files: vec![FtwArtifact {
+                path: "bin/x86_64-unknown-linux-gnu/libgame.so".to_string(),
+                size: 8,
+                sha256: "d1b2a59fbea7e20077af9f91b27e95e865061b270be03ff539ab3b73587882e8"
+                    .to_string(),
+            }],
+        };
+        let json = serde_json::to_string(&manifest)?;
+        assert!(json.contains(r#""kind":"game""#));
+        assert_eq!(manifest, serde_json::from_str(&json)?);
+        assert_eq!(
+            PathBuf::from("bin/x86_64-unknown-linux-gnu/game.release.manifest.json"),
+            manifest.get_path()
+        );
+        let lib_manifest = FtwManifest {
+            kind: FtwManifestKind::Lib,
+            ..manifest
+        };
+        assert_eq!(
+            PathBuf::from("lib/x86_64-unknown-linux-gnu/game.manifest.json"),
+            lib_manifest.get_path()
+        );
+        let debug_lib_manifest = FtwManifest {
+            build_type: "debug".to_string(),
+            ..lib_manifest.clone()
+        };
+        assert_eq!(lib_manifest.get_path(), debug_lib_manifest.get_path());
+        Ok(())
+    }
+
+    #[test]
+    fn test_artifact_status_fmt() {
+        assert_eq!("ok", format!("{}", FtwArtifactStatus::Ok));
+        assert_eq!("modified", format!("{}", FtwArtifactStatus::Modified));
+        assert_eq!("missing", format!("{}", FtwArtifactStatus::Missing));
+    }
+}
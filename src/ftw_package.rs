@@ -0,0 +1,353 @@
+use crate::ftw_build_type::FtwBuildType;
+use crate::ftw_error::FtwError;
+use crate::ftw_manifest::FtwManifest;
+use crate::ftw_target::FtwTarget;
+use crate::traits::{ToAppExt, ToCliArg, ToStrTarget};
+use crate::util;
+use flate2::write::GzEncoder;
+use flate2::{Compression, GzBuilder};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, DateTime, ZipWriter};
+
+// 1980-01-01T00:00:00Z, the earliest timestamp a zip archive can hold
+const ARCHIVE_MTIME: u64 = 315_532_800;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FtwArchiveFormat {
+    Zip,
+    TarGz,
+}
+
+impl From<FtwTarget> for FtwArchiveFormat {
+    fn from(target: FtwTarget) -> Self {
+        if target.is_linux() {
+            FtwArchiveFormat::TarGz
+        } else {
+            FtwArchiveFormat::Zip
+        }
+    }
+}
+
+impl Display for FtwArchiveFormat {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let ext = match self {
+            FtwArchiveFormat::Zip => "zip",
+            FtwArchiveFormat::TarGz => "tar.gz",
+        };
+        write!(f, "{ext}")
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwPackageEntry {
+    pub archive_path: String,
+    pub source: PathBuf,
+    pub is_executable: bool,
+}
+
+/// A distributable archive of an exported game
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwPackage {
+    pub name: String,
+    pub format: FtwArchiveFormat,
+    pub entries: Vec<FtwPackageEntry>,
+}
+
+impl FtwPackage {
+    /// Collects the files listed in the manifest of the exported game for
+    /// `target` and `build_type` at the root of the archive plus the
+    /// `extra_files` of the project at `root` at their path relative to the
+    /// project
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the crate cannot be read, the manifest of the exported game is missing, a file is missing or outside of the project or two files have the same path inside the archive
+    pub fn create(
+        root: &Path,
+        target: FtwTarget,
+        build_type: FtwBuildType,
+        extra_files: &[String],
+    ) -> Result<Self, FtwError> {
+        let crate_name = util::get_crate_name_from_path(&root.join("rust"))?;
+        let crate_version = util::get_crate_version_from_path(&root.join("rust"))?;
+        let target_cli_arg = target.to_cli_arg();
+        let manifest_path = format!("bin/{target_cli_arg}/{crate_name}.{build_type}.manifest.json");
+        let manifest = FtwManifest::load(&root.join(manifest_path))?;
+        let app_file = format!(
+            "{crate_name}.{build_type}.{target_cli_arg}{}",
+            target.to_app_ext()
+        );
+        let str_target = target.to_str_target();
+        let name = if build_type.is_release() {
+            format!("{crate_name}-{crate_version}-{str_target}")
+        } else {
+            format!("{crate_name}-{crate_version}-{str_target}-{build_type}")
+        };
+        let game_files = manifest.files.iter().map(|artifact| {
+            let source = PathBuf::from(&artifact.path);
+            let relative_path = source.file_name().map(PathBuf::from).unwrap_or_default();
+            (source, relative_path)
+        });
+        let extra_files = extra_files
+            .iter()
+            .map(|extra_file| {
+                let source = FtwPackage::get_extra_file_path(extra_file)
+                    .ok_or_else(|| FtwError::InvalidPackageFile(extra_file.to_string()))?;
+                Ok((source.clone(), source))
+            })
+            .collect::<Result<Vec<(PathBuf, PathBuf)>, FtwError>>()?;
+        let mut entries = game_files
+            .chain(extra_files)
+            .map(|(source, relative_path)| {
+                let source_display = source.display().to_string().replace('\\', "/");
+                if !root.join(&source).is_file() {
+                    return Err(FtwError::MissingPackageFile(source_display));
+                }
+                let relative_path = relative_path
+                    .to_str()
+                    .ok_or(FtwError::StringConversionError)?
+                    .replace('\\', "/");
+                Ok(FtwPackageEntry {
+                    archive_path: format!("{name}/{relative_path}"),
+                    is_executable: relative_path == app_file,
+                    source,
+                })
+            })
+            .collect::<Result<Vec<FtwPackageEntry>, FtwError>>()?;
+        entries.sort_by(|a, b| a.archive_path.cmp(&b.archive_path));
+        entries.dedup();
+        FtwPackage::check_collisions(&entries)?;
+        Ok(FtwPackage {
+            name,
+            format: target.into(),
+            entries,
+        })
+    }
+
+    /// The path of an extra file relative to the project root once its `.`
+    /// and `..` are resolved, `None` if it is absolute or outside of the root
+    fn get_extra_file_path(extra_file: &str) -> Option<PathBuf> {
+        Path::new(extra_file)
+            .components()
+            .try_fold(PathBuf::new(), |mut path, component| {
+                match component {
+                    Component::Normal(name) => path.push(name),
+                    Component::CurDir => {}
+                    Component::ParentDir if path.pop() => {}
+                    Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                        return None;
+                    }
+                }
+                Some(path)
+            })
+    }
+
+    fn check_collisions(entries: &[FtwPackageEntry]) -> Result<(), FtwError> {
+        match entries
+            .windows(2)
+            .find(|pair| pair[0].archive_path == pair[1].archive_path)
+        {
+            Some(pair) => Err(FtwError::PackageEntryCollision(
+                pair[0].source.display().to_string(),
+                pair[1].source.display().to_string(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    #[must_use]
+    pub fn get_path(&self) -> PathBuf {
+        let name = &self.name;
+        let format = self.format;
+        PathBuf::from(format!("bin/{name}.{format}"))
+    }
+
+    /// Writes the archive inside the project at `root` with every entry in
+    /// order and with a fixed timestamp, owner and permissions so that it can
+    /// be reproduced
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the archive cannot be written
+    pub fn write(&self, root: &Path) -> Result<PathBuf, FtwError> {
+        let path = self.get_path();
+        let file = File::create(root.join(&path))?;
+        match self.format {
+            FtwArchiveFormat::Zip => self.write_zip(root, file)?,
+            FtwArchiveFormat::TarGz => self.write_tar_gz(root, file)?,
+        }
+        Ok(path)
+    }
+
+    fn write_zip(&self, root: &Path, file: File) -> Result<(), FtwError> {
+        let mut zip = ZipWriter::new(file);
+        for entry in &self.entries {
+            let options = FileOptions::default()
+                .compression_method(CompressionMethod::Deflated)
+                .last_modified_time(DateTime::default())
+                .unix_permissions(if entry.is_executable { 0o755 } else { 0o644 });
+            zip.start_file(entry.archive_path.as_str(), options)?;
+            zip.write_all(&fs::read(root.join(&entry.source))?)?;
+        }
+        zip.finish()?;
+        Ok(())
+    }
+
+    fn write_tar_gz(&self, root: &Path, file: File) -> Result<(), FtwError> {
+        let encoder: GzEncoder<File> = GzBuilder::new()
+            .mtime(0)
+            .write(file, Compression::default());
+        let mut tar = tar::Builder::new(encoder);
+        for entry in &self.entries {
+            let contents = fs::read(root.join(&entry.source))?;
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(if entry.is_executable { 0o755 } else { 0o644 });
+            header.set_mtime(ARCHIVE_MTIME);
+            header.set_uid(0);
+            header.set_gid(0);
+            header.set_entry_type(tar::EntryType::Regular);
+            header.set_cksum();
+            tar.append_data(&mut header, &entry.archive_path, contents.as_slice())?;
+        }
+        tar.into_inner()?.finish()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod ftw_package_tests {
+    use super::*;
+    use crate::ftw_manifest::FtwManifestKind;
+    use crate::test_util::create_project_skeleton;
+    use std::env;
+
+    #[test]
+    fn test_archive_format() {
+        assert_eq!(FtwArchiveFormat::TarGz, FtwTarget::LinuxX86_64.into());
+        assert_eq!(FtwArchiveFormat::Zip, FtwTarget::WindowsX86_64Gnu.into());
+        assert_eq!(FtwArchiveFormat::Zip, FtwTarget::MacOsAarch64.into());
+        assert_eq!("tar.gz", format!("{}", FtwArchiveFormat::TarGz));
+        assert_eq!("zip", format!("{}", FtwArchiveFormat::Zip));
+    }
+
+    #[test]
+    fn test_write_is_reproducible() -> Result<(), FtwError> {
+        let dir = env::temp_dir().join(format!("ftw-package-{}", std::process::id()));
+        fs::create_dir_all(dir.join("bin"))?;
+        fs::write(dir.join("game.exe"), b"game")?;
+        fs::write(dir.join("game.pck"), b"pack")?;
+        for format in [FtwArchiveFormat::Zip, FtwArchiveFormat::TarGz] {
+            let package = FtwPackage {
+                name: "game-0.1.0-windows-x86_64-gnu".to_string(),
+                format,
+                entries: vec![
+                    FtwPackageEntry {
+                        archive_path: "game-0.1.0-windows-x86_64-gnu/game.exe".to_string(),
+                        source: PathBuf::from("game.exe"),
+                        is_executable: true,
+                    },
+                    FtwPackageEntry {
+                        archive_path: "game-0.1.0-windows-x86_64-gnu/game.pck".to_string(),
+                        source: PathBuf::from("game.pck"),
+                        is_executable: false,
+                    },
+                ],
+            };
+            let path = dir.join(package.write(&dir)?);
+            let first = fs::read(&path)?;
+            fs::write(dir.join("game.exe"), b"game")?;
+            let second = fs::read(dir.join(package.write(&dir)?))?;
+            assert_eq!(first, second);
+        }
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_extra_file_path() {
+        assert_eq!(
+            Some(PathBuf::from("docs/README.md")),
+            FtwPackage::get_extra_file_path("docs/README.md")
+        );
+        assert_eq!(
+            Some(PathBuf::from("README.md")),
+            FtwPackage::get_extra_file_path("./docs/../README.md")
+        );
+        assert_eq!(None, FtwPackage::get_extra_file_path("../secrets.txt"));
+        assert_eq!(
+            None,
+            FtwPackage::get_extra_file_path("docs/../../secrets.txt")
+        );
+        assert_eq!(None, FtwPackage::get_extra_file_path("/etc/passwd"));
+    }
+
+    #[test]
+    fn test_create_rejects_files_outside_of_the_project() -> Result<(), FtwError> {
+        let root = env::temp_dir().join(format!("ftw-package-outside-{}", std::process::id()));
+        create_project_skeleton(&root)?;
+        fs::write(
+            root.join("rust/Cargo.toml"),
+            "[package]\nname = \"game\"\nversion = \"0.1.0\"\n",
+        )?;
+        let target = FtwTarget::LinuxX86_64;
+        let bin_dir = root.join("bin").join(target.to_cli_arg());
+        fs::write(
+            bin_dir.join("game.debug.x86_64-unknown-linux-gnu.x86_64"),
+            "game",
+        )?;
+        FtwManifest::create(
+            &root,
+            FtwManifestKind::Game,
+            target,
+            FtwBuildType::Debug,
+            None,
+        )?
+        .save(&root)?;
+        fs::write(root.join("README.md"), "readme")?;
+        let outside_file = format!("../ftw-package-outside-{}.txt", std::process::id());
+        fs::write(root.join(&outside_file), "secret")?;
+        let package = FtwPackage::create(
+            &root,
+            target,
+            FtwBuildType::Debug,
+            &["README.md".to_string()],
+        )?;
+        assert_eq!(2, package.entries.len());
+        assert!(matches!(
+            FtwPackage::create(&root, target, FtwBuildType::Debug, std::slice::from_ref(&outside_file)),
+            Err(FtwError::InvalidPackageFile(file)) if file == outside_file
+        ));
+        fs::remove_file(root.join(&outside_file))?;
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_collisions() {
+        let entry = |archive_path: &str, source: &str| FtwPackageEntry {
+            archive_path: archive_path.to_string(),
+            source: PathBuf::from(source),
+            is_executable: false,
+        };
+        let entries = [
+            entry("game/README.md", "README.md"),
+            entry("game/docs/README.md", "docs/README.md"),
+        ];
+        assert!(FtwPackage::check_collisions(&entries).is_ok());
+        let entries = [
+            entry("game/game.pck", "bin/x86_64-unknown-linux-gnu/game.pck"),
+            entry("game/game.pck", "game.pck"),
+        ];
+        assert!(matches!(
+            FtwPackage::check_collisions(&entries),
+            Err(FtwError::PackageEntryCollision(first, second))
+                if first == "bin/x86_64-unknown-linux-gnu/game.pck" && second == "game.pck"
+        ));
+    }
+}
@@ -0,0 +1,438 @@
+use crate::ftw_build_type::FtwBuildType;
+use crate::ftw_error::FtwError;
+use crate::ftw_target::FtwTarget;
+use crate::traits::{ToAppExt, ToCliArg, ToExportName};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use strum::IntoEnumIterator;
+
+pub const EXPORT_PRESETS_FILE: &str = "godot/export_presets.cfg";
+
+/// A single `[preset.N]` and its `[preset.N.options]` section
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FtwPreset {
+    pub index: usize,
+    pub fields: Vec<(String, String)>,
+    pub options: Vec<(String, String)>,
+}
+
+/// A field of a preset that differs from what ftw expects
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwPresetDrift {
+    pub name: String,
+    pub key: String,
+    pub expected: String,
+    pub actual: Option<String>,
+}
+
+/// The export presets of a project, the original contents are kept as is so
+/// that the presets customised by the user are written back untouched
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FtwPresets {
+    pub contents: String,
+    pub presets: Vec<FtwPreset>,
+}
+
+impl FtwPreset {
+    /// Creates the preset that `FtwCompiler::export` expects for `target`
+    /// and `build_type`
+    #[must_use]
+    pub fn expected(
+        index: usize,
+        target: FtwTarget,
+        build_type: FtwBuildType,
+        crate_name: &str,
+    ) -> Self {
+        let export_name = target.to_export_name();
+        let target_cli_arg = target.to_cli_arg();
+        let target_app_ext = target.to_app_ext();
+        let fields = [
+            ("name", quote(&FtwPreset::get_name(target, build_type))),
+            ("platform", quote(&export_name)),
+            ("runnable", "false".to_string()),
+            ("custom_features", quote(&target_cli_arg)),
+            ("export_filter", quote("all_resources")),
+            ("include_filter", quote("")),
+            ("exclude_filter", quote("")),
+            ("export_path", quote(&format!("../bin/{target_cli_arg}/{crate_name}.{build_type}.{target_cli_arg}{target_app_ext}"))),
+            ("script_export_mode", "1".to_string()),
+            ("script_encryption_key", quote("")),
+        ];
+        let is_64_bits =
+            target_cli_arg.starts_with("x86_64") || target_cli_arg.starts_with("aarch64");
+        let options: Vec<(&str, String)> = match target {
+            t if t.is_linux() || t.is_windows() => vec![
+                ("binary_format/64_bits", is_64_bits.to_string()),
+                ("binary_format/embed_pck", "false".to_string()),
+            ],
+            t if t.is_android() => vec![
+                (
+                    "architectures/armeabi-v7a",
+                    (t == FtwTarget::AndroidLinuxArmV7).to_string(),
+                ),
+                (
+                    "architectures/arm64-v8a",
+                    (t == FtwTarget::AndroidLinuxAarch64).to_string(),
+                ),
+                (
+                    "architectures/x86",
+                    (t == FtwTarget::AndroidLinuxX86).to_string(),
+                ),
+                (
+                    "architectures/x86_64",
+                    (t == FtwTarget::AndroidLinuxX86_64).to_string(),
+                ),
+            ],
+            _ => vec![],
+        };
+        let to_owned_pairs = |pairs: Vec<(&str, String)>| {
+            pairs
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect()
+        };
+        FtwPreset {
+            index,
+            fields: to_owned_pairs(fields.to_vec()),
+            options: to_owned_pairs(options),
+        }
+    }
+
+    #[must_use]
+    pub fn get_name(target: FtwTarget, build_type: FtwBuildType) -> String {
+        let export_name = target.to_export_name();
+        let target_cli_arg = target.to_cli_arg();
+        format!("{export_name}.{target_cli_arg}.{build_type}")
+    }
+
+    #[must_use]
+    pub fn get_field(&self, key: &str) -> Option<&str> {
+        get_value(&self.fields, key)
+    }
+
+    #[must_use]
+    pub fn get_option(&self, key: &str) -> Option<&str> {
+        get_value(&self.options, key)
+    }
+
+    #[must_use]
+    pub fn get_preset_name(&self) -> Option<String> {
+        self.get_field("name").map(unquote)
+    }
+
+    /// Compares the fields that ftw relies on when exporting, every other
+    /// field belongs to the user
+    #[must_use]
+    pub fn get_drifts(&self, expected: &FtwPreset) -> Vec<FtwPresetDrift> {
+        let name = expected.get_preset_name().unwrap_or_default();
+        let managed_fields = ["platform", "export_path", "custom_features"];
+        let field_drifts = managed_fields.iter().filter_map(|key| {
+            let expected_value = expected.get_field(key)?;
+            let actual_value = self.get_field(key);
+            let is_same = match (*key, actual_value) {
+                ("custom_features", Some(actual_value)) => unquote(actual_value)
+                    .split(',')
+                    .any(|feature| feature.trim() == unquote(expected_value)),
+                (_, actual_value) => actual_value == Some(expected_value),
+            };
+            (!is_same).then(|| FtwPresetDrift {
+                name: name.clone(),
+                key: (*key).to_string(),
+                expected: expected_value.to_string(),
+                actual: actual_value.map(String::from),
+            })
+        });
+        let option_drifts = expected.options.iter().filter_map(|(key, expected_value)| {
+            let actual_value = self.get_option(key);
+            (actual_value != Some(expected_value.as_str())).then(|| FtwPresetDrift {
+                name: name.clone(),
+                key: format!("options/{key}"),
+                expected: expected_value.clone(),
+                actual: actual_value.map(String::from),
+            })
+        });
+        field_drifts.chain(option_drifts).collect()
+    }
+
+    #[must_use]
+    pub fn to_cfg(&self) -> String {
+        let index = self.index;
+        let to_lines = |pairs: &[(String, String)]| {
+            pairs
+                .iter()
+                .map(|(key, value)| format!("{key}={value}\n"))
+                .collect::<String>()
+        };
+        let fields = to_lines(&self.fields);
+        let options = to_lines(&self.options);
+        format!("[preset.{index}]\n\n{fields}\n[preset.{index}.options]\n\n{options}")
+    }
+}
+
+impl FtwPresets {
+    /// # Errors
+    ///
+    /// Will return `Err` if the regular expressions cannot be compiled
+    pub fn parse(contents: &str) -> Result<Self, FtwError> {
+        let section_re = Regex::new(r"^\[preset\.([0-9]+)(\.options)?\]$")?;
+        let pair_re = Regex::new(r"^([A-Za-z0-9_/\-.]+)=(.*)$")?;
+        let mut presets: Vec<FtwPreset> = vec![];
+        let mut section: Option<(usize, bool)> = None;
+        for line in contents.lines() {
+            let line = line.trim_end();
+            if line.starts_with('[') {
+                section = section_re.captures(line).and_then(|captures| {
+                    let index = captures.get(1)?.as_str().parse().ok()?;
+                    Some((index, captures.get(2).is_some()))
+                });
+                if let Some((index, _)) = section {
+                    if !presets.iter().any(|preset| preset.index == index) {
+                        presets.push(FtwPreset {
+                            index,
+                            ..FtwPreset::default()
+                        });
+                    }
+                }
+                continue;
+            }
+            let (Some((index, is_options)), Some(captures)) = (section, pair_re.captures(line))
+            else {
+                continue;
+            };
+            let pair = (captures[1].to_string(), captures[2].to_string());
+            if let Some(preset) = presets.iter_mut().find(|preset| preset.index == index) {
+                if is_options {
+                    preset.options.push(pair);
+                } else {
+                    preset.fields.push(pair);
+                }
+            }
+        }
+        Ok(FtwPresets {
+            contents: contents.to_string(),
+            presets,
+        })
+    }
+
+    /// Loads the export presets of the project, a missing file has no presets
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the file exists but cannot be read
+    pub fn load(path: &Path) -> Result<Self, FtwError> {
+        if !path.exists() {
+            return Ok(FtwPresets::default());
+        }
+        FtwPresets::parse(&fs::read_to_string(path)?)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the file cannot be written
+    pub fn save(&self, path: &Path) -> Result<(), FtwError> {
+        fs::write(path, &self.contents)?;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&FtwPreset> {
+        self.presets
+            .iter()
+            .find(|preset| preset.get_preset_name().as_deref() == Some(name))
+    }
+
+    /// Sets the option `key` of the preset `name` to `value` and keeps every
+    /// other line as is, returns `false` if there is no such preset
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the presets cannot be parsed again
+    pub fn set_option(&mut self, name: &str, key: &str, value: &str) -> Result<bool, FtwError> {
+        let Some(index) = self.get(name).map(|preset| preset.index) else {
+            return Ok(false);
+        };
+        let header = format!("[preset.{index}.options]");
+        let prefix = format!("{key}=");
+        let option = format!("{prefix}{value}");
+        let mut lines: Vec<String> = self.contents.lines().map(String::from).collect();
+        match lines.iter().position(|line| line.trim_end() == header) {
+            Some(start) => {
+                let end = lines[start + 1..]
+                    .iter()
+                    .position(|line| line.starts_with('['))
+                    .map_or(lines.len(), |position| start + 1 + position);
+                let existing = (start + 1..end).find(|&i| lines[i].starts_with(&prefix));
+                let last = (start + 1..end)
+                    .rev()
+                    .find(|&i| !lines[i].trim().is_empty());
+                match (existing, last) {
+                    (Some(i), _) => lines[i] = option,
+                    (None, Some(i)) => lines.insert(i + 1, option),
+                    (None, None) => {
+                        lines.insert(start + 1, String::new());
+                        lines.insert(start + 2, option);
+                    }
+                }
+            }
+            None => lines.extend([String::new(), header, String::new(), option]),
+        }
+        let mut contents = lines.join("\n");
+        contents.push('\n');
+        *self = FtwPresets::parse(&contents)?;
+        Ok(true)
+    }
+
+    /// Appends a preset for every target and build type that has none, the
+    /// existing presets are left as they are and only their drifts are
+    /// returned
+    pub fn sync(&mut self, crate_name: &str) -> (Vec<String>, Vec<FtwPresetDrift>) {
+        let mut added = vec![];
+        let mut drifts = vec![];
+        for target in FtwTarget::iter() {
+            for build_type in FtwBuildType::iter() {
+                let next_index = self
+                    .presets
+                    .iter()
+                    .map(|preset| preset.index + 1)
+                    .max()
+                    .unwrap_or_default();
+                let expected = FtwPreset::expected(next_index, target, build_type, crate_name);
+                let name = FtwPreset::get_name(target, build_type);
+                if let Some(preset) = self.get(&name) {
+                    drifts.append(&mut preset.get_drifts(&expected));
+                } else {
+                    if !self.contents.is_empty() && !self.contents.ends_with("\n\n") {
+                        let separator = if self.contents.ends_with('\n') {
+                            "\n"
+                        } else {
+                            "\n\n"
+                        };
+                        self.contents.push_str(separator);
+                    }
+                    self.contents.push_str(&expected.to_cfg());
+                    self.presets.push(expected);
+                    added.push(name);
+                }
+            }
+        }
+        (added, drifts)
+    }
+}
+
+fn get_value<'a>(pairs: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    pairs
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, value)| value.as_str())
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{value}\"")
+}
+
+fn unquote(value: &str) -> String {
+    value.trim_matches('"').to_string()
+}
+
+#[cfg(test)]
+mod ftw_presets_tests {
+    use super::*;
+
+    const PRESETS: &str = r#"[preset.0]
+
+name="Linux/X11.x86_64-unknown-linux-gnu.debug"
+platform="Linux/X11"
+runnable=true
+custom_features="x86_64-unknown-linux-gnu,demo"
+export_filter="all_resources"
+include_filter="*.json"
+exclude_filter=""
+export_path="../bin/x86_64-unknown-linux-gnu/game.debug.x86_64-unknown-linux-gnu"
+script_export_mode=1
+script_encryption_key=""
+
+[preset.0.options]
+
+custom_template/debug=""
+binary_format/64_bits=true
+binary_format/embed_pck=false
+"#;
+
+    #[test]
+    fn test_parse() -> Result<(), FtwError> {
+        let presets = FtwPresets::parse(PRESETS)?;
+        assert_eq!(1, presets.presets.len());
+        let preset = presets
+            .get("Linux/X11.x86_64-unknown-linux-gnu.debug")
+            .ok_or(FtwError::PathError)?;
+        assert_eq!(Some("\"*.json\""), preset.get_field("include_filter"));
+        assert_eq!(Some("true"), preset.get_option("binary_format/64_bits"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sync() -> Result<(), FtwError> {
+        let mut presets = FtwPresets::parse(PRESETS)?;
+        let (added, drifts) = presets.sync("game");
+        assert_eq!(25, added.len());
+        assert!(!added.contains(&"Linux/X11.x86_64-unknown-linux-gnu.debug".to_string()));
+        assert!(added.contains(&"Windows Desktop.i686-pc-windows-gnu.release".to_string()));
+        assert_eq!(
+            vec![FtwPresetDrift {
+                name: "Linux/X11.x86_64-unknown-linux-gnu.debug".to_string(),
+                key: "export_path".to_string(),
+                expected:
+                    "\"../bin/x86_64-unknown-linux-gnu/game.debug.x86_64-unknown-linux-gnu.x86_64\""
+                        .to_string(),
+                actual: Some(
+                    "\"../bin/x86_64-unknown-linux-gnu/game.debug.x86_64-unknown-linux-gnu\""
+                        .to_string()
+                ),
+            }],
+            drifts
+        );
+        assert!(presets.contents.starts_with(PRESETS));
+        assert!(presets
+            .contents
+            .contains("\n\n[preset.1]\n\nname=\"Android.aarch64-linux-android.debug\""));
+        let reparsed = FtwPresets::parse(&presets.contents)?;
+        assert_eq!(presets.presets, reparsed.presets);
+        let (added, _) = presets.sync("game");
+        assert!(added.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_option() -> Result<(), FtwError> {
+        let mut presets = FtwPresets::parse(PRESETS)?;
+        let name = "Linux/X11.x86_64-unknown-linux-gnu.debug";
+        assert!(presets.set_option(name, "binary_format/embed_pck", "true")?);
+        assert!(presets.set_option(name, "texture_format/etc", "false")?);
+        assert!(!presets.set_option("Missing", "texture_format/etc", "false")?);
+        let preset = presets.get(name).ok_or(FtwError::PathError)?;
+        assert_eq!(Some("true"), preset.get_option("binary_format/embed_pck"));
+        assert_eq!(Some("false"), preset.get_option("texture_format/etc"));
+        assert_eq!(Some("\"\""), preset.get_option("custom_template/debug"));
+        assert!(presets
+            .contents
+            .ends_with("binary_format/embed_pck=true\ntexture_format/etc=false\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_expected() {
+        let preset =
+            FtwPreset::expected(3, FtwTarget::WindowsX86Gnu, FtwBuildType::Release, "game");
+        assert_eq!(
+            Some("\"Windows Desktop.i686-pc-windows-gnu.release\""),
+            preset.get_field("name")
+        );
+        assert_eq!(
+            Some("\"i686-pc-windows-gnu\""),
+            preset.get_field("custom_features")
+        );
+        assert_eq!(Some("false"), preset.get_option("binary_format/64_bits"));
+        assert!(preset.to_cfg().starts_with("[preset.3]\n\nname="));
+    }
+}
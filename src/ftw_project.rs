@@ -0,0 +1,343 @@
+use crate::ftw_build_type::FtwBuildType;
+use crate::ftw_command::FtwCommand;
+use crate::ftw_configuration::FtwConfiguration;
+use crate::ftw_error::FtwError;
+use crate::ftw_event::{FtwEvent, FtwListener};
+use crate::ftw_machine_type::FtwMachineType;
+use crate::ftw_node_type::FtwNodeType;
+use crate::ftw_run_options::FtwRunOptions;
+use crate::ftw_target::FtwTarget;
+use crate::util;
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use voca_rs::Voca;
+
+/// The files of a class added with [`FtwProject::add_class`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwClassFiles {
+    pub rs_file: PathBuf,
+    pub gdns_file: PathBuf,
+    pub tscn_file: PathBuf,
+}
+
+/// A library built with [`FtwProject::build`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FtwBuild {
+    pub target: FtwTarget,
+    pub build_type: FtwBuildType,
+    pub is_up_to_date: bool,
+}
+
+/// A game exported with [`FtwProject::export`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwExport {
+    pub target: FtwTarget,
+    pub build_type: FtwBuildType,
+    pub path: PathBuf,
+    pub is_up_to_date: bool,
+}
+
+/// A game started with [`FtwProject::run`], it keeps running until it exits
+/// or the child is killed
+#[derive(Debug)]
+pub struct FtwGame {
+    pub child: Child,
+    pub is_up_to_date: bool,
+}
+
+/// A godot-rust project rooted at an explicit path, it lets other tools add
+/// classes, build, export and run the game without going through the command
+/// line, the progress is reported to the listener of the project
+pub struct FtwProject {
+    root: PathBuf,
+    ftw_cfg: FtwConfiguration,
+    listener: Box<dyn FtwListener>,
+}
+
+impl FtwProject {
+    /// Opens the project at `root` with the given configuration, use
+    /// [`FtwConfiguration::from_root`] to read the `.ftw` file of the project
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `root` cannot be found or it is not a valid project
+    pub fn open(root: impl AsRef<Path>, ftw_cfg: FtwConfiguration) -> Result<Self, FtwError> {
+        let root = root.as_ref().canonicalize()?;
+        let project = FtwProject {
+            root,
+            ftw_cfg,
+            listener: Box::new(|_: &FtwEvent| {}),
+        };
+        project.validate()?;
+        Ok(project)
+    }
+
+    #[must_use]
+    pub fn with_listener(mut self, listener: impl FtwListener + 'static) -> Self {
+        self.listener = Box::new(listener);
+        self
+    }
+
+    #[must_use]
+    pub fn get_root(&self) -> &Path {
+        &self.root
+    }
+
+    #[must_use]
+    pub fn get_configuration(&self) -> &FtwConfiguration {
+        &self.ftw_cfg
+    }
+
+    fn validate(&self) -> Result<(), FtwError> {
+        FtwCommand::is_valid_project(&self.root, self.listener.as_ref())?;
+        Ok(())
+    }
+
+    /// Adds the rust module, `.gdns` and `.tscn` files of a class, the class
+    /// name can contain the directories of the class (`enemies/Goblin`)
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the project is not valid or the files cannot be created
+    pub fn add_class(
+        &self,
+        class_name: &str,
+        node_type: FtwNodeType,
+    ) -> Result<FtwClassFiles, FtwError> {
+        self.validate()?;
+        let root = self.root.as_path();
+        let listener = self.listener.as_ref();
+        let (class_name, directories) = util::get_class_name_and_directories(class_name);
+        FtwCommand::create_class_rs_file(
+            root,
+            &class_name,
+            &directories,
+            node_type,
+            &[],
+            &[],
+            listener,
+        )?;
+        FtwCommand::create_gdns_file(root, &class_name, &directories, node_type, listener)?;
+        FtwCommand::create_tscn_file(
+            root,
+            &class_name,
+            &directories,
+            node_type,
+            &[],
+            None,
+            &[],
+            listener,
+        )?;
+        FtwCommand::create_lib_rs_file(root, &class_name, node_type, listener)?;
+        let get_path = |base_path: &str, file_name: String| {
+            directories
+                .iter()
+                .fold(root.join(base_path), |path, directory| path.join(directory))
+                .join(file_name)
+        };
+        let class_name_snake_case = class_name._snake_case();
+        let class_name_pascal_case = class_name._pascal_case();
+        Ok(FtwClassFiles {
+            rs_file: get_path("rust/src", format!("{class_name_snake_case}.rs")),
+            gdns_file: get_path("godot/native", format!("{class_name_pascal_case}.gdns")),
+            tscn_file: get_path("godot/scenes", format!("{class_name_pascal_case}.tscn")),
+        })
+    }
+
+    /// Builds the library for every target and installs it in `lib/`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the project is not valid or a library cannot be built
+    pub fn build(
+        &self,
+        targets: &[FtwTarget],
+        build_type: FtwBuildType,
+    ) -> Result<Vec<FtwBuild>, FtwError> {
+        self.validate()?;
+        let listener = self.listener.as_ref();
+        let session = self.ftw_cfg.get_container_session(&self.root);
+        targets
+            .iter()
+            .map(|target| {
+                let target = *target;
+                let is_lib_installed = FtwCommand::build_lib(
+                    &self.root,
+                    &self.ftw_cfg,
+                    &session,
+                    target,
+                    build_type,
+                    listener,
+                )?;
+                let is_up_to_date = !is_lib_installed;
+                listener.on_event(&FtwEvent::LibraryBuilt {
+                    target,
+                    build_type,
+                    is_up_to_date,
+                });
+                Ok(FtwBuild {
+                    target,
+                    build_type,
+                    is_up_to_date,
+                })
+            })
+            .collect()
+    }
+
+    /// Builds the library and exports the game for every target into `bin/`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the project is not valid, an android keystore is missing or a game cannot be exported
+    pub fn export(
+        &self,
+        targets: &[FtwTarget],
+        build_type: FtwBuildType,
+    ) -> Result<Vec<FtwExport>, FtwError> {
+        self.validate()?;
+        let listener = self.listener.as_ref();
+        FtwCommand::check_keystores(&self.root, targets, build_type)?;
+        let session = self.ftw_cfg.get_container_session(&self.root);
+        targets
+            .iter()
+            .map(|target| {
+                let target = *target;
+                let is_lib_installed = FtwCommand::build_lib(
+                    &self.root,
+                    &self.ftw_cfg,
+                    &session,
+                    target,
+                    build_type,
+                    listener,
+                )?;
+                let is_exported = FtwCommand::export_game(
+                    &self.root,
+                    &self.ftw_cfg,
+                    &session,
+                    target,
+                    build_type,
+                    is_lib_installed,
+                    listener,
+                )?;
+                let is_up_to_date = !is_exported;
+                listener.on_event(&FtwEvent::GameExported {
+                    target,
+                    build_type,
+                    is_up_to_date,
+                });
+                let compiler = self
+                    .ftw_cfg
+                    .get_compiler(&self.root, target, build_type, &session);
+                Ok(FtwExport {
+                    target,
+                    build_type,
+                    path: self.root.join(compiler.get_export_path()?),
+                    is_up_to_date,
+                })
+            })
+            .collect()
+    }
+
+    /// Builds the library for the current platform and starts the game
+    /// without waiting for it to exit
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the project is not valid, the library cannot be built or the game cannot be started
+    pub fn run(
+        &self,
+        machine_type: FtwMachineType,
+        build_type: FtwBuildType,
+        options: &FtwRunOptions,
+    ) -> Result<FtwGame, FtwError> {
+        self.validate()?;
+        let listener = self.listener.as_ref();
+        let godot_args = options.to_godot_args(&self.root)?;
+        let target: FtwTarget = util::get_current_platform().parse().unwrap_or_default();
+        let mut godot_command =
+            FtwCommand::get_godot_command(&self.root, &self.ftw_cfg, &machine_type, &godot_args)?;
+        let session = self.ftw_cfg.get_container_session(&self.root);
+        let is_lib_installed = FtwCommand::build_lib(
+            &self.root,
+            &self.ftw_cfg,
+            &session,
+            target,
+            build_type,
+            listener,
+        )?;
+        session.stop();
+        let child = godot_command.current_dir(&self.root).spawn()?;
+        listener.on_event(&FtwEvent::GameStarted { machine_type });
+        Ok(FtwGame {
+            child,
+            is_up_to_date: !is_lib_installed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod ftw_project_tests {
+    use super::*;
+    use crate::test_util::create_project_skeleton;
+    use std::env;
+    use std::fs;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_open() -> Result<(), FtwError> {
+        let root = env::temp_dir().join(format!("ftw-project-{}", std::process::id()));
+        fs::create_dir_all(&root)?;
+        let current_dir = env::current_dir()?;
+        assert!(matches!(
+            FtwProject::open(&root, FtwConfiguration::default()),
+            Err(FtwError::InvalidProject(_))
+        ));
+        assert_eq!(current_dir, env::current_dir()?);
+        create_project_skeleton(&root)?;
+        let project = FtwProject::open(&root, FtwConfiguration::default())?;
+        assert_eq!(root.canonicalize()?, project.get_root());
+        assert_eq!(&FtwConfiguration::default(), project.get_configuration());
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_class() -> Result<(), FtwError> {
+        let root = env::temp_dir().join(format!("ftw-project-class-{}", std::process::id()));
+        create_project_skeleton(&root)?;
+        let current_dir = env::current_dir()?;
+        let events = Arc::new(Mutex::new(vec![]));
+        let listener_events = Arc::clone(&events);
+        let project = FtwProject::open(&root, FtwConfiguration::default())?.with_listener(
+            move |event: &FtwEvent| listener_events.lock().unwrap().push(event.clone()),
+        );
+        let class_files = project.add_class("enemies/Goblin", FtwNodeType::KinematicBody2D)?;
+        assert_eq!(current_dir, env::current_dir()?);
+        let root = root.canonicalize()?;
+        assert_eq!(
+            FtwClassFiles {
+                rs_file: root.join("rust/src/enemies/goblin.rs"),
+                gdns_file: root.join("godot/native/enemies/Goblin.gdns"),
+                tscn_file: root.join("godot/scenes/enemies/Goblin.tscn"),
+            },
+            class_files
+        );
+        assert!(class_files.rs_file.exists());
+        assert!(class_files.gdns_file.exists());
+        assert!(class_files.tscn_file.exists());
+        assert!(fs::read_to_string(root.join("rust/src/lib.rs"))?.contains("mod enemies;"));
+        assert_eq!(
+            vec![
+                FtwEvent::ProjectValidated,
+                FtwEvent::FileCreated("rust/src/enemies/goblin.rs".to_string()),
+                FtwEvent::FileCreated("rust/src/enemies/mod.rs".to_string()),
+                FtwEvent::FileCreated("godot/native/enemies/Goblin.gdns".to_string()),
+                FtwEvent::FileCreated("godot/scenes/enemies/Goblin.tscn".to_string()),
+                FtwEvent::FileCreated("rust/src/lib.rs".to_string()),
+            ],
+            *events.lock().unwrap()
+        );
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+}
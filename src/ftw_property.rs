@@ -0,0 +1,274 @@
+use crate::ftw_error::FtwError;
+use crate::ftw_node_type::FtwNodeType;
+use liquid::{object, Object};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The value types that can be exported as they are, with their rust and
+/// godot (`.tscn`/`.tres`) default values and their variant type
+const VALUE_TYPES: [(&str, &str, &str, &str); 11] = [
+    ("bool", "false", "false", "Bool"),
+    ("i32", "0", "0", "I64"),
+    ("i64", "0", "0", "I64"),
+    ("f32", "0.0", "0.0", "F64"),
+    ("f64", "0.0", "0.0", "F64"),
+    ("String", "String::new()", "\"\"", "GodotString"),
+    ("GodotString", "GodotString::new()", "\"\"", "GodotString"),
+    (
+        "NodePath",
+        "NodePath::default()",
+        "NodePath(\"\")",
+        "NodePath",
+    ),
+    ("Vector2", "Vector2::ZERO", "Vector2( 0, 0 )", "Vector2"),
+    ("Vector3", "Vector3::ZERO", "Vector3( 0, 0, 0 )", "Vector3"),
+    (
+        "Color",
+        "Color::from_rgba(0.0, 0.0, 0.0, 1.0)",
+        "Color( 0, 0, 0, 1 )",
+        "Color",
+    ),
+];
+
+/// An exported property declared on the command line as `name:type` or
+/// `name:type=default`, the type is either a value type (`i32`, `String`,
+/// `Vector2`...) or a godot class (`Texture`, `PackedScene`...) which is
+/// exported as an optional reference and cannot have a default
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwProperty {
+    pub name: String,
+    pub property_type: String,
+    pub default: Option<String>,
+}
+
+impl FtwProperty {
+    /// Whether `name` can be the name of a property, a signal or a parameter
+    pub(crate) fn is_valid_name(name: &str) -> bool {
+        name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+
+    fn get_value_type(&self) -> Option<(&str, &str, &str, &str)> {
+        VALUE_TYPES
+            .into_iter()
+            .find(|(value_type, _, _, _)| *value_type == self.property_type)
+    }
+
+    /// The rust and godot literals of the default given on the command line,
+    /// vectors and colors are given as comma separated numbers (`1,2`)
+    fn get_default_literals(&self) -> Option<(String, String)> {
+        let default = self.default.as_deref()?;
+        let numbers = default
+            .split(',')
+            .map(|number| number.trim().parse::<f64>().ok())
+            .collect::<Option<Vec<f64>>>()
+            .unwrap_or_default();
+        let same_literals = |literal: String| Some((literal.clone(), literal));
+        match (self.property_type.as_str(), numbers.as_slice()) {
+            ("bool", _) => default
+                .parse::<bool>()
+                .ok()
+                .map(|value| value.to_string())
+                .and_then(same_literals),
+            ("i32", _) => default
+                .parse::<i32>()
+                .ok()
+                .map(|value| value.to_string())
+                .and_then(same_literals),
+            ("i64", _) => default
+                .parse::<i64>()
+                .ok()
+                .map(|value| value.to_string())
+                .and_then(same_literals),
+            ("f32" | "f64", [value]) => same_literals(format!("{value:?}")),
+            ("String", _) => Some((format!("String::from({default:?})"), format!("{default:?}"))),
+            ("GodotString", _) => Some((
+                format!("GodotString::from({default:?})"),
+                format!("{default:?}"),
+            )),
+            ("NodePath", _) => Some((
+                format!("NodePath::from_str({default:?})"),
+                format!("NodePath({default:?})"),
+            )),
+            ("Vector2", [x, y]) => Some((
+                format!("Vector2::new({x:?}, {y:?})"),
+                format!("Vector2( {x}, {y} )"),
+            )),
+            ("Vector3", [x, y, z]) => Some((
+                format!("Vector3::new({x:?}, {y:?}, {z:?})"),
+                format!("Vector3( {x}, {y}, {z} )"),
+            )),
+            ("Color", [r, g, b]) => Some((
+                format!("Color::from_rgb({r:?}, {g:?}, {b:?})"),
+                format!("Color( {r}, {g}, {b}, 1 )"),
+            )),
+            ("Color", [r, g, b, a]) => Some((
+                format!("Color::from_rgba({r:?}, {g:?}, {b:?}, {a:?})"),
+                format!("Color( {r}, {g}, {b}, {a} )"),
+            )),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn to_rust_type(&self) -> String {
+        match self.get_value_type() {
+            Some((value_type, _, _, _)) => value_type.to_string(),
+            None => format!("Option<Ref<{}>>", self.property_type),
+        }
+    }
+
+    #[must_use]
+    pub fn to_rust_default(&self) -> String {
+        match (self.get_default_literals(), self.get_value_type()) {
+            (Some((rust_default, _)), _) => rust_default,
+            (None, Some((_, rust_default, _, _))) => rust_default.to_string(),
+            (None, None) => "None".to_string(),
+        }
+    }
+
+    #[must_use]
+    pub fn to_godot_default(&self) -> String {
+        match (self.get_default_literals(), self.get_value_type()) {
+            (Some((_, godot_default)), _) => godot_default,
+            (None, Some((_, _, godot_default, _))) => godot_default.to_string(),
+            (None, None) => "null".to_string(),
+        }
+    }
+
+    /// The `VariantType` of the property when it is the parameter of a signal
+    #[must_use]
+    pub fn to_variant_type(&self) -> String {
+        match self.get_value_type() {
+            Some((_, _, _, variant_type)) => variant_type.to_string(),
+            None => "Object".to_string(),
+        }
+    }
+
+    /// The globals of the property in the class, `.tscn` and `.tres` templates
+    #[must_use]
+    pub fn to_tmpl_object(&self) -> Object {
+        object!({
+            "name": self.name,
+            "rust_type": self.to_rust_type(),
+            "rust_default": self.to_rust_default(),
+            "godot_default": self.to_godot_default(),
+            "variant_type": self.to_variant_type(),
+            "has_default": self.default.is_some(),
+        })
+    }
+}
+
+impl FromStr for FtwProperty {
+    type Err = FtwError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid_property = || FtwError::InvalidProperty(s.to_string());
+        let (name, property_type) = s.split_once(':').ok_or_else(invalid_property)?;
+        let (property_type, default) = match property_type.split_once('=') {
+            Some((property_type, default)) => (property_type, Some(default.to_string())),
+            None => (property_type, None),
+        };
+        let property = FtwProperty {
+            name: name.to_string(),
+            property_type: property_type.to_string(),
+            default,
+        };
+        let is_godot_class = property_type
+            .parse::<FtwNodeType>()
+            .map(|node_type| node_type.to_string())
+            == Ok(property_type.to_string());
+        let is_valid_type = match property.get_value_type() {
+            Some(_) => property.default.is_none() || property.get_default_literals().is_some(),
+            None => is_godot_class && property.default.is_none(),
+        };
+        if FtwProperty::is_valid_name(name) && is_valid_type {
+            Ok(property)
+        } else {
+            Err(invalid_property())
+        }
+    }
+}
+
+impl Display for FtwProperty {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match &self.default {
+            Some(default) => write!(f, "{}:{}={default}", self.name, self.property_type),
+            None => write!(f, "{}:{}", self.name, self.property_type),
+        }
+    }
+}
+
+#[cfg(test)]
+mod ftw_property_tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() -> Result<(), FtwError> {
+        let property: FtwProperty = "damage:i32".parse()?;
+        assert_eq!(
+            FtwProperty {
+                name: "damage".to_string(),
+                property_type: "i32".to_string(),
+                default: None,
+            },
+            property
+        );
+        assert_eq!("damage:i32", property.to_string());
+        let property: FtwProperty = "icon:Texture".parse()?;
+        assert_eq!("icon", property.name);
+        assert_eq!("Texture", property.property_type);
+        let property: FtwProperty = "speed:f32=200".parse()?;
+        assert_eq!(Some("200".to_string()), property.default);
+        assert_eq!("speed:f32=200", property.to_string());
+        for invalid_property in [
+            "damage",
+            "damage:",
+            "Damage:i32",
+            "damage:u128",
+            ":i32",
+            "icon:Sprite3",
+            "health:i64=full",
+            "offset:Vector2=1",
+            "icon:Texture=res://icon.png",
+        ] {
+            assert!(matches!(
+                invalid_property.parse::<FtwProperty>(),
+                Err(FtwError::InvalidProperty(property)) if property == invalid_property
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_rust_and_godot() -> Result<(), FtwError> {
+        let property: FtwProperty = "speed:f32".parse()?;
+        assert_eq!("f32", property.to_rust_type());
+        assert_eq!("0.0", property.to_rust_default());
+        assert_eq!("0.0", property.to_godot_default());
+        let property: FtwProperty = "title:String".parse()?;
+        assert_eq!("String", property.to_rust_type());
+        assert_eq!("String::new()", property.to_rust_default());
+        assert_eq!("\"\"", property.to_godot_default());
+        let property: FtwProperty = "icon:Texture".parse()?;
+        assert_eq!("Option<Ref<Texture>>", property.to_rust_type());
+        assert_eq!("None", property.to_rust_default());
+        assert_eq!("null", property.to_godot_default());
+        assert_eq!("Object", property.to_variant_type());
+        let property: FtwProperty = "speed:f32=200".parse()?;
+        assert_eq!("200.0", property.to_rust_default());
+        assert_eq!("200.0", property.to_godot_default());
+        assert_eq!("F64", property.to_variant_type());
+        let property: FtwProperty = "title:String=Hero".parse()?;
+        assert_eq!("String::from(\"Hero\")", property.to_rust_default());
+        assert_eq!("\"Hero\"", property.to_godot_default());
+        let property: FtwProperty = "offset:Vector2=1.5,2".parse()?;
+        assert_eq!("Vector2::new(1.5, 2.0)", property.to_rust_default());
+        assert_eq!("Vector2( 1.5, 2 )", property.to_godot_default());
+        let property: FtwProperty = "tint:Color=1,0,0".parse()?;
+        assert_eq!("Color::from_rgb(1.0, 0.0, 0.0)", property.to_rust_default());
+        assert_eq!("Color( 1, 0, 0, 1 )", property.to_godot_default());
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use crate::ftw_error::FtwError;
+use crate::util;
+use std::path::Path;
+
+/// The options that are passed to godot when running the game
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FtwRunOptions {
+    pub scene: Option<String>,
+    pub verbose: bool,
+    pub debug_collisions: bool,
+    pub debug_navigation: bool,
+    pub user_args: Vec<String>,
+}
+
+impl FtwRunOptions {
+    /// Maps the scene to a `res://` path, a class name is mapped to the scene
+    /// that `ftw class` creates for it (e.g. `enemies/Goblin` to
+    /// `res://scenes/enemies/Goblin.tscn`)
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the scene does not exist inside `godot/` of the project at `root`
+    pub fn get_scene_path(&self, root: &Path) -> Result<Option<String>, FtwError> {
+        let Some(scene) = &self.scene else {
+            return Ok(None);
+        };
+        let scene_path = util::get_scene_path(scene);
+        if root.join("godot").join(&scene_path).is_file() {
+            Ok(Some(format!("res://{scene_path}")))
+        } else {
+            Err(FtwError::MissingScene(scene.clone()))
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the scene does not exist inside `godot/` of the project at `root`
+    pub fn to_godot_args(&self, root: &Path) -> Result<Vec<String>, FtwError> {
+        let flags = [
+            (self.verbose, "--verbose"),
+            (self.debug_collisions, "--debug-collisions"),
+            (self.debug_navigation, "--debug-navigation"),
+        ];
+        let mut args: Vec<String> = flags
+            .iter()
+            .filter(|(is_enabled, _)| *is_enabled)
+            .map(|(_, flag)| (*flag).to_string())
+            .collect();
+        args.extend(self.get_scene_path(root)?);
+        if !self.user_args.is_empty() {
+            args.push("--".to_string());
+            args.extend(self.user_args.iter().cloned());
+        }
+        Ok(args)
+    }
+}
+
+#[cfg(test)]
+mod ftw_run_options_tests {
+    use super::*;
+
+    #[test]
+    fn test_to_godot_args() -> Result<(), FtwError> {
+        let options = FtwRunOptions {
+            scene: None,
+            verbose: true,
+            debug_collisions: false,
+            debug_navigation: true,
+            user_args: vec!["--level".to_string(), "2".to_string()],
+        };
+        assert_eq!(
+            vec!["--verbose", "--debug-navigation", "--", "--level", "2"],
+            options.to_godot_args(Path::new("."))?
+        );
+        assert!(FtwRunOptions::default()
+            .to_godot_args(Path::new("."))?
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_scene_path_missing() {
+        let class_names = [
+            "enemies/Goblin",
+            "scenes/Level2.tscn",
+            "res://scenes/Level2.tscn",
+        ];
+        for class_name in class_names {
+            let options = FtwRunOptions {
+                scene: Some(class_name.to_string()),
+                ..FtwRunOptions::default()
+            };
+            assert!(matches!(
+                options.get_scene_path(Path::new(".")),
+                Err(FtwError::MissingScene(scene)) if scene == class_name
+            ));
+        }
+    }
+}
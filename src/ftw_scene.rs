@@ -0,0 +1,484 @@
+use crate::ftw_error::FtwError;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs::{read_to_string, write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const SECTION_REG_EX: &str = r"^\[(\w+)(.*)\]$";
+const ATTRIBUTE_REG_EX: &str = r#"(\w+)=("[^"]*"|\w+\( *[^)]*\)|[^\s\]]+)"#;
+
+/// An `ext_resource` of a scene
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwExtResource {
+    pub id: String,
+    pub path: String,
+    pub resource_type: String,
+}
+
+/// A node of a scene, the root node has no parent and an instanced node has
+/// the `res://` path of its scene instead of a type
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwSceneNode {
+    pub name: String,
+    pub node_type: Option<String>,
+    pub parent: Option<String>,
+    pub instance: Option<String>,
+}
+
+impl FtwSceneNode {
+    /// The path of the node from the root node (`Sprite/Anim`), `.` for the
+    /// root node
+    #[must_use]
+    pub fn get_path(&self) -> String {
+        match self.parent.as_deref() {
+            None => ".".to_string(),
+            Some(".") => self.name.clone(),
+            Some(parent) => format!("{parent}/{}", self.name),
+        }
+    }
+}
+
+/// A `[tag attribute=value...]` line of a scene and its line index
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct FtwSceneSection {
+    index: usize,
+    tag: String,
+    attributes: HashMap<String, String>,
+}
+
+/// The sections and properties of a `.tscn` file, the lines that are not
+/// changed are written back as they were
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwScene {
+    lines: Vec<String>,
+    sections: Vec<FtwSceneSection>,
+}
+
+impl FtwScene {
+    /// # Errors
+    ///
+    /// Will return `Err` if the scene cannot be found
+    pub fn load(path: &Path) -> Result<Self, FtwError> {
+        let contents =
+            read_to_string(path).map_err(|_| FtwError::MissingScene(path.display().to_string()))?;
+        contents.parse()
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the scene cannot be written
+    pub fn save(&self, path: &Path) -> Result<(), FtwError> {
+        Ok(write(path, self.to_string())?)
+    }
+
+    /// Maps a `res://` path to its path inside the project at `root`
+    #[must_use]
+    pub fn get_project_path(root: &Path, res_path: &str) -> PathBuf {
+        root.join("godot")
+            .join(res_path.strip_prefix("res://").unwrap_or(res_path))
+    }
+
+    fn parse_sections(lines: &[String]) -> Result<Vec<FtwSceneSection>, FtwError> {
+        let section_reg_ex = Regex::new(SECTION_REG_EX)?;
+        let attribute_reg_ex = Regex::new(ATTRIBUTE_REG_EX)?;
+        let sections = lines
+            .iter()
+            .enumerate()
+            .filter_map(|(index, line)| {
+                let captures = section_reg_ex.captures(line.trim())?;
+                let attributes = attribute_reg_ex
+                    .captures_iter(&captures[2])
+                    .map(|attribute| {
+                        let value = attribute[2].trim_matches('"').to_string();
+                        (attribute[1].to_string(), value)
+                    })
+                    .collect();
+                Some(FtwSceneSection {
+                    index,
+                    tag: captures[1].to_string(),
+                    attributes,
+                })
+            })
+            .collect();
+        Ok(sections)
+    }
+
+    fn insert_line(&mut self, index: usize, line: String) -> Result<(), FtwError> {
+        self.lines.insert(index, line);
+        self.sections = FtwScene::parse_sections(&self.lines)?;
+        Ok(())
+    }
+
+    fn get_sections(&self, tag: &str) -> impl Iterator<Item = &FtwSceneSection> {
+        let tag = tag.to_string();
+        self.sections
+            .iter()
+            .filter(move |section| section.tag == tag)
+    }
+
+    #[must_use]
+    pub fn get_ext_resources(&self) -> Vec<FtwExtResource> {
+        self.get_sections("ext_resource")
+            .filter_map(|section| {
+                let attributes = &section.attributes;
+                Some(FtwExtResource {
+                    id: attributes.get("id")?.clone(),
+                    path: attributes.get("path")?.clone(),
+                    resource_type: attributes.get("type").cloned().unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+
+    fn get_ext_resource_path(&self, value: &str) -> Option<String> {
+        let id = value
+            .strip_prefix("ExtResource(")?
+            .strip_suffix(')')?
+            .trim()
+            .to_string();
+        self.get_ext_resources()
+            .into_iter()
+            .find(|ext_resource| ext_resource.id == id)
+            .map(|ext_resource| ext_resource.path)
+    }
+
+    fn get_node_sections(&self) -> Vec<(usize, FtwSceneNode)> {
+        self.get_sections("node")
+            .filter_map(|section| {
+                let attributes = &section.attributes;
+                let node = FtwSceneNode {
+                    name: attributes.get("name")?.clone(),
+                    node_type: attributes.get("type").cloned(),
+                    parent: attributes.get("parent").cloned(),
+                    instance: attributes
+                        .get("instance")
+                        .and_then(|instance| self.get_ext_resource_path(instance)),
+                };
+                Some((section.index, node))
+            })
+            .collect()
+    }
+
+    #[must_use]
+    pub fn get_nodes(&self) -> Vec<FtwSceneNode> {
+        self.get_node_sections()
+            .into_iter()
+            .map(|(_, node)| node)
+            .collect()
+    }
+
+    #[must_use]
+    pub fn get_root(&self) -> Option<FtwSceneNode> {
+        self.get_nodes()
+            .into_iter()
+            .find(|node| node.parent.is_none())
+    }
+
+    /// The type of `node`, the type of an instanced node is the type of the
+    /// root node of its scene and the type of a node that overrides a node of
+    /// an inherited or instanced scene is the type of the overridden node, the
+    /// scenes are loaded from the project at `root`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the scene of an instanced node cannot be found, it has no root node or it has no overridden node
+    pub fn get_node_type(&self, root: &Path, node: &FtwSceneNode) -> Result<String, FtwError> {
+        match (&node.node_type, &node.instance) {
+            (Some(node_type), _) => Ok(node_type.clone()),
+            (None, Some(instance)) => {
+                let scene = FtwScene::load(&FtwScene::get_project_path(root, instance))?;
+                let scene_root = scene
+                    .get_root()
+                    .ok_or_else(|| FtwError::InvalidScene(instance.clone()))?;
+                scene.get_node_type(root, &scene_root)
+            }
+            (None, None) => Ok(self
+                .get_overridden_node_type(root, &node.get_path())?
+                .unwrap_or_else(|| "Node".to_string())),
+        }
+    }
+
+    /// The type of the node at `path` in the scene of the nearest instanced
+    /// ancestor of `path`, an inherited scene is the instance of its root node,
+    /// `None` if `path` has no instanced ancestor
+    fn get_overridden_node_type(
+        &self,
+        root: &Path,
+        path: &str,
+    ) -> Result<Option<String>, FtwError> {
+        let instanced_ancestor = self
+            .get_nodes()
+            .into_iter()
+            .filter_map(|ancestor| {
+                let instance = ancestor.instance.clone()?;
+                let relative_path = match ancestor.get_path().as_str() {
+                    "." => path.to_string(),
+                    ancestor_path => path.strip_prefix(&format!("{ancestor_path}/"))?.to_string(),
+                };
+                Some((instance, relative_path))
+            })
+            .min_by_key(|(_, relative_path)| relative_path.len());
+        let Some((instance, relative_path)) = instanced_ancestor else {
+            return Ok(None);
+        };
+        let scene = FtwScene::load(&FtwScene::get_project_path(root, &instance))?;
+        let overridden_node = scene
+            .get_nodes()
+            .into_iter()
+            .find(|scene_node| scene_node.get_path() == relative_path);
+        match overridden_node {
+            Some(overridden_node) => scene.get_node_type(root, &overridden_node).map(Some),
+            None => scene
+                .get_overridden_node_type(root, &relative_path)?
+                .ok_or(FtwError::MissingNode(relative_path, instance))
+                .map(Some),
+        }
+    }
+
+    fn get_properties(&self, section_index: usize) -> &[String] {
+        let properties = &self.lines[section_index + 1..];
+        let end = properties
+            .iter()
+            .position(|line| line.starts_with('['))
+            .unwrap_or(properties.len());
+        &properties[..end]
+    }
+
+    /// Adds an `ext_resource` after the last one and updates `load_steps`,
+    /// returns its id
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the scene cannot be parsed after adding the `ext_resource`
+    pub fn add_ext_resource(
+        &mut self,
+        path: &str,
+        resource_type: &str,
+    ) -> Result<String, FtwError> {
+        let id = self
+            .get_sections("ext_resource")
+            .filter_map(|section| section.attributes.get("id")?.parse::<usize>().ok())
+            .max()
+            .unwrap_or_default()
+            + 1;
+        let last_index = self
+            .get_sections("ext_resource")
+            .last()
+            .map(|section| section.index);
+        let ext_resource =
+            format!("[ext_resource path=\"{path}\" type=\"{resource_type}\" id={id}]");
+        match last_index {
+            Some(index) => self.insert_line(index + 1, ext_resource)?,
+            None => {
+                self.insert_line(1, String::new())?;
+                self.insert_line(2, ext_resource)?;
+            }
+        }
+        self.increment_load_steps();
+        Ok(id.to_string())
+    }
+
+    fn increment_load_steps(&mut self) {
+        let load_steps = self
+            .get_sections("gd_scene")
+            .next()
+            .and_then(|section| section.attributes.get("load_steps")?.parse::<usize>().ok());
+        if let Some(header) = self.lines.first_mut() {
+            *header = match load_steps {
+                Some(load_steps) => header.replace(
+                    &format!("load_steps={load_steps}"),
+                    &format!("load_steps={}", load_steps + 1),
+                ),
+                None => header.replacen(" format=", " load_steps=2 format=", 1),
+            };
+        }
+    }
+
+    /// Sets the script of the root node
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the scene has no root node or the root node already has a script
+    pub fn attach_script(&mut self, script_path: &str, scene_path: &str) -> Result<(), FtwError> {
+        let get_root_index = |scene: &FtwScene| {
+            scene
+                .get_node_sections()
+                .into_iter()
+                .find(|(_, node)| node.parent.is_none())
+                .map(|(index, _)| index)
+                .ok_or_else(|| FtwError::InvalidScene(scene_path.to_string()))
+        };
+        let has_script = self
+            .get_properties(get_root_index(self)?)
+            .iter()
+            .any(|property| property.starts_with("script = "));
+        if has_script {
+            return Err(FtwError::SceneHasScript(scene_path.to_string()));
+        }
+        let id = self.add_ext_resource(script_path, "Script")?;
+        let root_index = get_root_index(self)?;
+        self.insert_line(root_index + 1, format!("script = ExtResource( {id} )"))
+    }
+}
+
+impl FromStr for FtwScene {
+    type Err = FtwError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<String> = s.split('\n').map(ToString::to_string).collect();
+        let sections = FtwScene::parse_sections(&lines)?;
+        Ok(FtwScene { lines, sections })
+    }
+}
+
+impl Display for FtwScene {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod ftw_scene_tests {
+    use super::*;
+
+    const BOSS_TSCN: &str = r#"[gd_scene load_steps=3 format=2]
+
+[ext_resource path="res://assets/boss.png" type="Texture" id=1]
+[ext_resource path="res://scenes/Weapon.tscn" type="PackedScene" id=2]
+
+[sub_resource type="CircleShape2D" id=1]
+radius = 16.0
+
+[node name="Boss" type="KinematicBody2D"]
+position = Vector2( 64, 32 )
+
+[node name="Sprite" type="Sprite" parent="."]
+texture = ExtResource( 1 )
+
+[node name="Anim" type="AnimationPlayer" parent="Sprite"]
+
+[node name="Weapon" parent="Sprite/Anim" instance=ExtResource( 2 )]
+
+[node name="CollisionShape2D" type="CollisionShape2D" parent="."]
+shape = SubResource( 1 )
+"#;
+
+    #[test]
+    fn test_get_ext_resources() -> Result<(), FtwError> {
+        let scene: FtwScene = BOSS_TSCN.parse()?;
+        assert_eq!(
+            vec![
+                FtwExtResource {
+                    id: "1".to_string(),
+                    path: "res://assets/boss.png".to_string(),
+                    resource_type: "Texture".to_string(),
+                },
+                FtwExtResource {
+                    id: "2".to_string(),
+                    path: "res://scenes/Weapon.tscn".to_string(),
+                    resource_type: "PackedScene".to_string(),
+                },
+            ],
+            scene.get_ext_resources()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_nodes() -> Result<(), FtwError> {
+        let scene: FtwScene = BOSS_TSCN.parse()?;
+        let nodes = scene.get_nodes();
+        let paths: Vec<String> = nodes.iter().map(FtwSceneNode::get_path).collect();
+        assert_eq!(
+            vec![
+                ".",
+                "Sprite",
+                "Sprite/Anim",
+                "Sprite/Anim/Weapon",
+                "CollisionShape2D"
+            ],
+            paths
+        );
+        assert_eq!(Some(nodes[0].clone()), scene.get_root());
+        assert_eq!(Some("KinematicBody2D".to_string()), nodes[0].node_type);
+        assert_eq!(None, nodes[3].node_type);
+        assert_eq!(
+            Some("res://scenes/Weapon.tscn".to_string()),
+            nodes[3].instance
+        );
+        assert_eq!(
+            "AnimationPlayer",
+            scene.get_node_type(Path::new("."), &nodes[2])?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_node_type_of_overridden_node() -> Result<(), FtwError> {
+        let root = std::env::temp_dir().join(format!("ftw-scene-{}", std::process::id()));
+        let scenes = root.join("godot/scenes");
+        std::fs::create_dir_all(&scenes)?;
+        write(scenes.join("Boss.tscn"), BOSS_TSCN)?;
+        write(
+            scenes.join("Weapon.tscn"),
+            "[gd_scene format=2]\n\n[node name=\"Weapon\" type=\"Area2D\"]\n\n[node name=\"Blade\" type=\"Sprite\" parent=\".\"]\n",
+        )?;
+        let scene: FtwScene = r#"[gd_scene load_steps=2 format=2]
+
+[ext_resource path="res://scenes/Boss.tscn" type="PackedScene" id=1]
+
+[node name="Minion" instance=ExtResource( 1 )]
+
+[node name="Sprite" parent="."]
+modulate = Color( 1, 0, 0, 1 )
+
+[node name="Blade" parent="Sprite/Anim/Weapon"]
+
+[node name="Shadow" parent="."]
+"#
+        .parse()?;
+        let node_types: Vec<Result<String, FtwError>> = scene
+            .get_nodes()
+            .iter()
+            .map(|node| scene.get_node_type(&root, node))
+            .collect();
+        std::fs::remove_dir_all(&root)?;
+        assert!(matches!(
+            node_types.as_slice(),
+            [Ok(boss), Ok(sprite), Ok(blade), Err(FtwError::MissingNode(shadow, _))]
+                if boss == "KinematicBody2D" && sprite == "Sprite" && blade == "Sprite" && shadow == "Shadow"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_attach_script() -> Result<(), FtwError> {
+        let mut scene: FtwScene = BOSS_TSCN.parse()?;
+        scene.attach_script("res://native/Boss.gdns", "Boss.tscn")?;
+        let contents = scene.to_string();
+        assert!(contents.starts_with("[gd_scene load_steps=4 format=2]\n"));
+        assert!(contents.contains("id=2]\n[ext_resource path=\"res://native/Boss.gdns\" type=\"Script\" id=3]\n\n[sub_resource"));
+        assert!(contents.contains("[node name=\"Boss\" type=\"KinematicBody2D\"]\nscript = ExtResource( 3 )\nposition = Vector2( 64, 32 )\n"));
+        assert!(contents.ends_with("shape = SubResource( 1 )\n"));
+        assert!(matches!(
+            scene.attach_script("res://native/Boss.gdns", "Boss.tscn"),
+            Err(FtwError::SceneHasScript(scene)) if scene == "Boss.tscn"
+        ));
+        let mut scene: FtwScene =
+            "[gd_scene format=2]\n\n[node name=\"Level\" type=\"Node2D\"]\n".parse()?;
+        scene.attach_script("res://native/Level.gdns", "Level.tscn")?;
+        assert_eq!(
+            "[gd_scene load_steps=2 format=2]\n\n[ext_resource path=\"res://native/Level.gdns\" type=\"Script\" id=1]\n\n[node name=\"Level\" type=\"Node2D\"]\nscript = ExtResource( 1 )\n",
+            scene.to_string()
+        );
+        let mut scene: FtwScene = "[gd_scene format=2]\n".parse()?;
+        assert!(matches!(
+            scene.attach_script("res://native/Level.gdns", "Level.tscn"),
+            Err(FtwError::InvalidScene(scene)) if scene == "Level.tscn"
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use crate::ftw_error::FtwError;
+use crate::ftw_property::FtwProperty;
+use liquid::{object, Object};
+use liquid_core::model::Value;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A signal declared on the command line as `name` or `name:type,type...`,
+/// the parameters are named after their position (`arg0`, `arg1`...) and
+/// accept the same types as [`FtwProperty`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwSignal {
+    pub name: String,
+    pub params: Vec<FtwProperty>,
+}
+
+impl FtwSignal {
+    /// The globals of the signal in the class template
+    #[must_use]
+    pub fn to_tmpl_object(&self) -> Object {
+        let params: Vec<Value> = self
+            .params
+            .iter()
+            .map(|param| Value::Object(param.to_tmpl_object()))
+            .collect();
+        object!({
+            "name": self.name,
+            "params": Value::Array(params),
+        })
+    }
+}
+
+impl FromStr for FtwSignal {
+    type Err = FtwError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid_signal = || FtwError::InvalidSignal(s.to_string());
+        let (name, param_types) = match s.split_once(':') {
+            Some((name, param_types)) => (name, param_types.split(',').collect()),
+            None => (s, vec![]),
+        };
+        if !FtwProperty::is_valid_name(name) {
+            return Err(invalid_signal());
+        }
+        let params = param_types
+            .iter()
+            .enumerate()
+            .map(|(index, param_type)| {
+                if param_type.contains('=') {
+                    Err(invalid_signal())
+                } else {
+                    format!("arg{index}:{param_type}")
+                        .parse()
+                        .map_err(|_| invalid_signal())
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(FtwSignal {
+            name: name.to_string(),
+            params,
+        })
+    }
+}
+
+impl Display for FtwSignal {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let param_types: Vec<&str> = self
+            .params
+            .iter()
+            .map(|param| param.property_type.as_str())
+            .collect();
+        if param_types.is_empty() {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{}:{}", self.name, param_types.join(","))
+        }
+    }
+}
+
+#[cfg(test)]
+mod ftw_signal_tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() -> Result<(), FtwError> {
+        let signal: FtwSignal = "died".parse()?;
+        assert_eq!(
+            FtwSignal {
+                name: "died".to_string(),
+                params: vec![],
+            },
+            signal
+        );
+        assert_eq!("died", signal.to_string());
+        let signal: FtwSignal = "hit:i64,Node".parse()?;
+        assert_eq!("hit", signal.name);
+        assert_eq!(
+            vec!["arg0:i64".parse::<FtwProperty>()?, "arg1:Node".parse()?],
+            signal.params
+        );
+        assert_eq!("hit:i64,Node", signal.to_string());
+        for invalid_signal in ["", "Died", "hit:", "hit:u128", "hit:i64=2", "hit:i64,"] {
+            assert!(matches!(
+                invalid_signal.parse::<FtwSignal>(),
+                Err(FtwError::InvalidSignal(signal)) if signal == invalid_signal
+            ));
+        }
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ use crate::ftw_node_type::FtwNodeType;
 use crate::ftw_tag::FtwTag;
 use crate::ftw_target::FtwTarget;
 use crate::ftw_template::FtwTemplate;
-use crate::traits::ToMessage;
+use crate::traits::{ToGitTag, ToMessage};
 use crate::type_alias::{ClassName, Message, ProjectName};
 use colored::{ColoredString, Colorize};
 
@@ -17,21 +17,74 @@ pub enum FtwSuccess<'a> {
     },
     Class {
         class_name: ClassName,
-        node_type: &'a FtwNodeType,
+        node_type: FtwNodeType,
     },
     Singleton {
         class_name: ClassName,
     },
+    Resource {
+        class_name: ClassName,
+        properties: usize,
+    },
+    Bindings {
+        class_name: ClassName,
+        nodes: usize,
+    },
+    Attach {
+        class_name: ClassName,
+        node_type: FtwNodeType,
+        scene: String,
+    },
     Run {
         machine_type: &'a FtwMachineType,
+        is_up_to_date: bool,
+    },
+    RunInstances {
+        instances: Vec<String>,
+        is_up_to_date: bool,
+    },
+    RunExported {
+        target: &'a FtwTarget,
+        build_type: &'a FtwBuildType,
+        is_up_to_date: bool,
     },
     Build {
         targets: &'a Vec<FtwTarget>,
         build_type: &'a FtwBuildType,
+        up_to_date_targets: Vec<FtwTarget>,
     },
     Export {
         targets: &'a Vec<FtwTarget>,
         build_type: &'a FtwBuildType,
+        up_to_date_targets: Vec<FtwTarget>,
+    },
+    Package {
+        build_type: &'a FtwBuildType,
+        archives: Vec<String>,
+    },
+    PresetsSync {
+        added: Vec<String>,
+        drifts: usize,
+    },
+    AndroidKeystore {
+        build_type: &'a FtwBuildType,
+        keystore: String,
+        presets: usize,
+    },
+    Test {
+        passed: usize,
+        ignored: usize,
+    },
+    Check {
+        tag: Option<String>,
+        fixed: usize,
+    },
+    Upgrade {
+        tag: &'a FtwTag,
+        changes: usize,
+    },
+    Artifacts {
+        manifests: usize,
     },
     Clean,
 }
@@ -42,6 +95,19 @@ impl FtwSuccess<'_> {
     fn get_styled_success() -> ColoredString {
         "SUCCESS:".bold().green()
     }
+
+    fn get_up_to_date_suffix(directory: &str, up_to_date_targets: &[FtwTarget]) -> String {
+        if up_to_date_targets.is_empty() {
+            String::new()
+        } else {
+            let up_to_date_targets: Vec<String> = up_to_date_targets
+                .iter()
+                .map(|target| format!("{directory}/{target}"))
+                .collect();
+            let styled_up_to_date_targets = up_to_date_targets.join(",").blue().bold().italic();
+            format!(" ({styled_up_to_date_targets} up to date)")
+        }
+    }
 }
 
 impl ToMessage for FtwSuccess<'_> {
@@ -71,13 +137,71 @@ impl ToMessage for FtwSuccess<'_> {
                 let styled_class_name = class_name.blue().bold().italic();
                 format!("A new singleton class has been created {styled_class_name}")
             }
-            FtwSuccess::Run { machine_type } => {
+            FtwSuccess::Bindings { class_name, nodes } => {
+                let styled_class_name = class_name.blue().bold().italic();
+                let styled_nodes = nodes.to_string().blue().bold().italic();
+                format!("The bindings of {styled_class_name} have been generated for {styled_nodes} node(s)")
+            }
+            FtwSuccess::Attach {
+                class_name,
+                node_type,
+                scene,
+            } => {
+                let styled_class_name = class_name.blue().bold().italic();
+                let styled_node_type = node_type.to_string().blue().bold().italic();
+                let styled_scene = scene.blue().bold().italic();
+                format!("A new class has been created {styled_class_name} using the {styled_node_type} node type and attached to {styled_scene}")
+            }
+            FtwSuccess::Resource {
+                class_name,
+                properties,
+            } => {
+                let styled_class_name = class_name.blue().bold().italic();
+                let styled_properties = properties.to_string().blue().bold().italic();
+                format!("A new resource class has been created {styled_class_name} with {styled_properties} exported property(ies)")
+            }
+            FtwSuccess::Run {
+                machine_type,
+                is_up_to_date,
+            } => {
                 let styled_machine_type = machine_type.to_string().blue().bold().italic();
-                format!("The game was run as a {styled_machine_type} application")
+                let up_to_date = if *is_up_to_date {
+                    " (the library was up to date)"
+                } else {
+                    ""
+                };
+                format!("The game was run as a {styled_machine_type} application{up_to_date}")
+            }
+            FtwSuccess::RunInstances {
+                instances,
+                is_up_to_date,
+            } => {
+                let styled_instances = instances.join(", ").blue().bold().italic();
+                let up_to_date = if *is_up_to_date {
+                    " (the library was up to date)"
+                } else {
+                    ""
+                };
+                format!("The game was run as {styled_instances}{up_to_date}")
+            }
+            FtwSuccess::RunExported {
+                target,
+                build_type,
+                is_up_to_date,
+            } => {
+                let styled_target = format!("bin/{target}").blue().bold().italic();
+                let styled_build_type = build_type.to_string().blue().bold().italic();
+                let up_to_date = if *is_up_to_date {
+                    " (the exported game was up to date)"
+                } else {
+                    ""
+                };
+                format!("The game exported at {styled_target} with a {styled_build_type} profile was run{up_to_date}")
             }
             FtwSuccess::Build {
                 targets,
                 build_type,
+                up_to_date_targets,
             } => {
                 let targets: Vec<String> = targets
                     .iter()
@@ -85,13 +209,15 @@ impl ToMessage for FtwSuccess<'_> {
                     .collect();
                 let styled_targets = targets.join(",").blue().bold().italic();
                 let styled_build_type = build_type.to_string().blue().bold().italic();
+                let up_to_date = FtwSuccess::get_up_to_date_suffix("lib", up_to_date_targets);
                 format!(
-                    "A library was created at {styled_targets} with a {styled_build_type} profile"
+                    "A library was created at {styled_targets} with a {styled_build_type} profile{up_to_date}"
                 )
             }
             FtwSuccess::Export {
                 targets,
                 build_type,
+                up_to_date_targets,
             } => {
                 let targets: Vec<String> = targets
                     .iter()
@@ -99,7 +225,64 @@ impl ToMessage for FtwSuccess<'_> {
                     .collect();
                 let styled_target = targets.join(",").blue().bold().italic();
                 let styled_build_type = build_type.to_string().blue().bold().italic();
-                format!("A game was created at {styled_target} with a {styled_build_type} profile")
+                let up_to_date = FtwSuccess::get_up_to_date_suffix("bin", up_to_date_targets);
+                format!("A game was created at {styled_target} with a {styled_build_type} profile{up_to_date}")
+            }
+            FtwSuccess::Package {
+                build_type,
+                archives,
+            } => {
+                let styled_archives = archives.join(",").blue().bold().italic();
+                let styled_build_type = build_type.to_string().blue().bold().italic();
+                format!(
+                    "A package was created at {styled_archives} with a {styled_build_type} profile"
+                )
+            }
+            FtwSuccess::PresetsSync { added, drifts } => {
+                let styled_added = added.len().to_string().blue().bold().italic();
+                let styled_drifts = drifts.to_string().blue().bold().italic();
+                format!("The export presets were synced ({styled_added} added, {styled_drifts} drifted)")
+            }
+            FtwSuccess::AndroidKeystore {
+                build_type,
+                keystore,
+                presets,
+            } => {
+                let styled_build_type = build_type.to_string().blue().bold().italic();
+                let styled_keystore = keystore.blue().bold().italic();
+                let styled_presets = presets.to_string().blue().bold().italic();
+                format!("The {styled_build_type} keystore {styled_keystore} was configured in {styled_presets} Android export preset(s)")
+            }
+            FtwSuccess::Test { passed, ignored } => {
+                let styled_passed = passed.to_string().blue().bold().italic();
+                let styled_ignored = ignored.to_string().blue().bold().italic();
+                format!("The tests passed ({styled_passed} passed, {styled_ignored} ignored)")
+            }
+            FtwSuccess::Check { tag, fixed } => {
+                let styled_tag = tag
+                    .as_deref()
+                    .unwrap_or("an unknown template version")
+                    .blue()
+                    .bold()
+                    .italic();
+                let styled_fixed = fixed.to_string().blue().bold().italic();
+                let fixed_paths = if *fixed > 0 {
+                    format!(" after fixing {styled_fixed} path(s)")
+                } else {
+                    String::new()
+                };
+                format!("The project created from {styled_tag} is valid{fixed_paths}")
+            }
+            FtwSuccess::Upgrade { tag, changes } => {
+                let styled_tag = tag.to_git_tag().blue().bold().italic();
+                let styled_changes = changes.to_string().blue().bold().italic();
+                format!(
+                    "The project was upgraded to {styled_tag} ({styled_changes} file(s) changed)"
+                )
+            }
+            FtwSuccess::Artifacts { manifests } => {
+                let styled_manifests = manifests.to_string().blue().bold().italic();
+                format!("The artifacts of {styled_manifests} manifest(s) were verified")
             }
             FtwSuccess::Clean => "The project is now clean from excess artifacts".to_string(),
         };
@@ -140,7 +323,7 @@ mod ftw_success_tests {
         let node_type = FtwNodeType::Area2D;
         let ftw_success_class_message = FtwSuccess::Class {
             class_name: class_name.clone(),
-            node_type: &node_type,
+            node_type,
         }
         .to_message();
         let styled_class_name = class_name.blue().bold().italic();
@@ -159,9 +342,45 @@ mod ftw_success_tests {
             format!("{ftw_success_singleton_message}")
         );
         //
+        let ftw_success_bindings_message = FtwSuccess::Bindings {
+            class_name: class_name.clone(),
+            nodes: 3,
+        }
+        .to_message();
+        let styled_nodes = "3".blue().bold().italic();
+        assert_eq!(
+            format!("{thumbs_up} {styled_success} The bindings of {styled_class_name} have been generated for {styled_nodes} node(s)"),
+            format!("{ftw_success_bindings_message}")
+        );
+        //
+        let ftw_success_attach_message = FtwSuccess::Attach {
+            class_name: class_name.clone(),
+            node_type: FtwNodeType::KinematicBody2D,
+            scene: "godot/scenes/Boss.tscn".to_string(),
+        }
+        .to_message();
+        let styled_node_type = "KinematicBody2D".blue().bold().italic();
+        let styled_scene = "godot/scenes/Boss.tscn".blue().bold().italic();
+        assert_eq!(
+            format!("{thumbs_up} {styled_success} A new class has been created {styled_class_name} using the {styled_node_type} node type and attached to {styled_scene}"),
+            format!("{ftw_success_attach_message}")
+        );
+        //
+        let ftw_success_resource_message = FtwSuccess::Resource {
+            class_name: class_name.clone(),
+            properties: 2,
+        }
+        .to_message();
+        let styled_properties = "2".blue().bold().italic();
+        assert_eq!(
+            format!("{thumbs_up} {styled_success} A new resource class has been created {styled_class_name} with {styled_properties} exported property(ies)"),
+            format!("{ftw_success_resource_message}")
+        );
+        //
         let machine_type = FtwMachineType::Desktop;
         let ftw_success_run_message = FtwSuccess::Run {
             machine_type: &machine_type,
+            is_up_to_date: false,
         }
         .to_message();
         let styled_machine_type = machine_type.to_string().blue().bold().italic();
@@ -172,6 +391,7 @@ mod ftw_success_tests {
         let machine_type = FtwMachineType::Server;
         let ftw_success_run_message = FtwSuccess::Run {
             machine_type: &machine_type,
+            is_up_to_date: false,
         }
         .to_message();
         let styled_machine_type = machine_type.to_string().blue().bold().italic();
@@ -186,6 +406,7 @@ mod ftw_success_tests {
         let ftw_success_build_debug_message = FtwSuccess::Build {
             targets: &targets,
             build_type: &debug,
+            up_to_date_targets: vec![],
         }
         .to_message();
         let styled_target = format!("lib/{target}").blue().bold().italic();
@@ -199,6 +420,7 @@ mod ftw_success_tests {
         let ftw_success_build_release_message = FtwSuccess::Build {
             targets: &targets,
             build_type: &release,
+            up_to_date_targets: vec![],
         }
         .to_message();
         let styled_release = release.to_string().blue().bold().italic();
@@ -212,6 +434,7 @@ mod ftw_success_tests {
         let ftw_success_export_debug_message = FtwSuccess::Export {
             targets: &targets,
             build_type: &debug,
+            up_to_date_targets: vec![],
         }
         .to_message();
         let styled_target = format!("bin/{target}").blue().bold().italic();
@@ -223,6 +446,7 @@ mod ftw_success_tests {
         let ftw_success_export_release_message = FtwSuccess::Export {
             targets: &targets,
             build_type: &release,
+            up_to_date_targets: vec![],
         }
         .to_message();
         assert_eq!(
@@ -230,6 +454,99 @@ mod ftw_success_tests {
             format!("{ftw_success_export_release_message}")
         );
         //
+        let ftw_success_package_message = FtwSuccess::Package {
+            build_type: &release,
+            archives: vec!["bin/game-0.1.0-linux-x86_64.tar.gz".to_string()],
+        }
+        .to_message();
+        let styled_archives = "bin/game-0.1.0-linux-x86_64.tar.gz".blue().bold().italic();
+        assert_eq!(
+            format!("{thumbs_up} {styled_success} A package was created at {styled_archives} with a {styled_release} profile"),
+            format!("{ftw_success_package_message}")
+        );
+        //
+        let ftw_success_presets_sync_message = FtwSuccess::PresetsSync {
+            added: vec!["Linux/X11.x86_64-unknown-linux-gnu.debug".to_string()],
+            drifts: 2,
+        }
+        .to_message();
+        let styled_added = "1".blue().bold().italic();
+        let styled_drifts = "2".blue().bold().italic();
+        assert_eq!(
+            format!("{thumbs_up} {styled_success} The export presets were synced ({styled_added} added, {styled_drifts} drifted)"),
+            format!("{ftw_success_presets_sync_message}")
+        );
+        //
+        let ftw_success_android_keystore_message = FtwSuccess::AndroidKeystore {
+            build_type: &debug,
+            keystore: "debug.keystore".to_string(),
+            presets: 4,
+        }
+        .to_message();
+        let styled_keystore = "debug.keystore".blue().bold().italic();
+        let styled_presets = "4".blue().bold().italic();
+        assert_eq!(
+            format!("{thumbs_up} {styled_success} The {styled_debug} keystore {styled_keystore} was configured in {styled_presets} Android export preset(s)"),
+            format!("{ftw_success_android_keystore_message}")
+        );
+        //
+        let ftw_success_test_message = FtwSuccess::Test {
+            passed: 12,
+            ignored: 1,
+        }
+        .to_message();
+        let styled_passed = "12".blue().bold().italic();
+        let styled_ignored = "1".blue().bold().italic();
+        assert_eq!(
+            format!("{thumbs_up} {styled_success} The tests passed ({styled_passed} passed, {styled_ignored} ignored)"),
+            format!("{ftw_success_test_message}")
+        );
+        //
+        let ftw_success_check_message = FtwSuccess::Check {
+            tag: Some("v1.5.0".to_string()),
+            fixed: 0,
+        }
+        .to_message();
+        let styled_tag = "v1.5.0".blue().bold().italic();
+        assert_eq!(
+            format!("{thumbs_up} {styled_success} The project created from {styled_tag} is valid"),
+            format!("{ftw_success_check_message}")
+        );
+        //
+        let ftw_success_check_fixed_message = FtwSuccess::Check {
+            tag: None,
+            fixed: 3,
+        }
+        .to_message();
+        let styled_tag = "an unknown template version".blue().bold().italic();
+        let styled_fixed = "3".blue().bold().italic();
+        assert_eq!(
+            format!("{thumbs_up} {styled_success} The project created from {styled_tag} is valid after fixing {styled_fixed} path(s)"),
+            format!("{ftw_success_check_fixed_message}")
+        );
+        //
+        let tag = FtwTag::Tagged {
+            git_tag: "v1.5.0".to_string(),
+        };
+        let ftw_success_upgrade_message = FtwSuccess::Upgrade {
+            tag: &tag,
+            changes: 4,
+        }
+        .to_message();
+        let styled_tag = "v1.5.0".blue().bold().italic();
+        let styled_changes = "4".blue().bold().italic();
+        assert_eq!(
+            format!("{thumbs_up} {styled_success} The project was upgraded to {styled_tag} ({styled_changes} file(s) changed)"),
+            format!("{ftw_success_upgrade_message}")
+        );
+        //
+        let ftw_success_artifacts_message = FtwSuccess::Artifacts { manifests: 2 }.to_message();
+        let styled_manifests = "2".blue().bold().italic();
+        assert_eq!(
+            format!("{thumbs_up} {styled_success} The artifacts of {styled_manifests} manifest(s) were verified"),
+            format!("{ftw_success_artifacts_message}")
+        );
+        //
         let ftw_success_clean_message = FtwSuccess::Clean.to_message();
         assert_eq!(
             format!("{thumbs_up} {styled_success} The project is now clean from excess artifacts"),
@@ -237,6 +554,88 @@ mod ftw_success_tests {
         );
     }
 
+    #[test]
+    fn test_up_to_date_to_message() {
+        let thumbs_up = FtwSuccess::THUMBS_UP;
+        let styled_success = FtwSuccess::get_styled_success();
+        let machine_type = FtwMachineType::Desktop;
+        let ftw_success_run_message = FtwSuccess::Run {
+            machine_type: &machine_type,
+            is_up_to_date: true,
+        }
+        .to_message();
+        let styled_machine_type = machine_type.to_string().blue().bold().italic();
+        assert_eq!(
+            format!("{thumbs_up} {styled_success} The game was run as a {styled_machine_type} application (the library was up to date)"),
+            format!("{ftw_success_run_message}")
+        );
+        //
+        let target = FtwTarget::LinuxX86_64;
+        let release = FtwBuildType::Release;
+        let ftw_success_run_exported_message = FtwSuccess::RunExported {
+            target: &target,
+            build_type: &release,
+            is_up_to_date: true,
+        }
+        .to_message();
+        let styled_target = "bin/x86_64-unknown-linux-gnu".blue().bold().italic();
+        let styled_release = release.to_string().blue().bold().italic();
+        assert_eq!(
+            format!("{thumbs_up} {styled_success} The game exported at {styled_target} with a {styled_release} profile was run (the exported game was up to date)"),
+            format!("{ftw_success_run_exported_message}")
+        );
+        //
+        let ftw_success_run_instances_message = FtwSuccess::RunInstances {
+            instances: vec![
+                "server".to_string(),
+                "client 1".to_string(),
+                "client 2".to_string(),
+            ],
+            is_up_to_date: true,
+        }
+        .to_message();
+        let styled_instances = "server, client 1, client 2".blue().bold().italic();
+        assert_eq!(
+            format!("{thumbs_up} {styled_success} The game was run as {styled_instances} (the library was up to date)"),
+            format!("{ftw_success_run_instances_message}")
+        );
+        //
+        let targets = vec![FtwTarget::LinuxX86_64, FtwTarget::WindowsX86_64Gnu];
+        let debug = FtwBuildType::Debug;
+        let ftw_success_build_message = FtwSuccess::Build {
+            targets: &targets,
+            build_type: &debug,
+            up_to_date_targets: vec![FtwTarget::WindowsX86_64Gnu],
+        }
+        .to_message();
+        let styled_targets = "lib/x86_64-unknown-linux-gnu,lib/x86_64-pc-windows-gnu"
+            .blue()
+            .bold()
+            .italic();
+        let styled_up_to_date_targets = "lib/x86_64-pc-windows-gnu".blue().bold().italic();
+        let styled_debug = debug.to_string().blue().bold().italic();
+        assert_eq!(
+            format!("{thumbs_up} {styled_success} A library was created at {styled_targets} with a {styled_debug} profile ({styled_up_to_date_targets} up to date)"),
+            format!("{ftw_success_build_message}")
+        );
+        //
+        let ftw_success_export_message = FtwSuccess::Export {
+            targets: &targets,
+            build_type: &debug,
+            up_to_date_targets: vec![FtwTarget::LinuxX86_64],
+        }
+        .to_message();
+        let styled_targets = "bin/x86_64-unknown-linux-gnu,bin/x86_64-pc-windows-gnu"
+            .blue()
+            .bold()
+            .italic();
+        let styled_up_to_date_targets = "bin/x86_64-unknown-linux-gnu".blue().bold().italic();
+        assert_eq!(
+            format!("{thumbs_up} {styled_success} A game was created at {styled_targets} with a {styled_debug} profile ({styled_up_to_date_targets} up to date)"),
+            format!("{ftw_success_export_message}")
+        );
+    }
+
     #[test]
     fn test_new_custom_template_to_message() {
         let new_game = "my-awesome-game".to_string();
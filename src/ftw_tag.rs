@@ -28,7 +28,7 @@ impl FromStr for FtwTag {
 impl ToGitTag for FtwTag {
     fn to_git_tag(&self) -> GitTag {
         match self {
-            FtwTag::Latest {} => DEFAULT_TEMPLATE_TAG,
+            FtwTag::Latest => DEFAULT_TEMPLATE_TAG,
             FtwTag::Tagged { git_tag } => git_tag,
         }
         .to_string()
@@ -38,7 +38,7 @@ impl ToGitTag for FtwTag {
 impl Display for FtwTag {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let message = match self {
-            FtwTag::Latest {} => DEFAULT_TEMPLATE_TAG,
+            FtwTag::Latest => DEFAULT_TEMPLATE_TAG,
             FtwTag::Tagged { git_tag } => git_tag,
         };
         write!(f, "{message}")
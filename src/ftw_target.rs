@@ -1,6 +1,8 @@
 use crate::ftw_error::FtwError;
-use crate::traits::{ToAppExt, ToCliArg, ToExportName, ToLibExt, ToLibPrefix, ToStrTarget};
-use crate::type_alias::{AppExt, CliArg, ExportName, LibExt, LibPrefix, StrTarget};
+use crate::traits::{
+    ToAppExt, ToCliArg, ToExportName, ToGdnlibFeatures, ToLibExt, ToLibPrefix, ToStrTarget,
+};
+use crate::type_alias::{AppExt, CliArg, ExportName, GdnlibFeatures, LibExt, LibPrefix, StrTarget};
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
@@ -26,21 +28,6 @@ pub enum FtwTarget {
 
 #[rustfmt::skip]
 impl FtwTarget {
-    fn is(self, target: FtwTarget) -> bool {
-        self == target
-    }
-
-    /// # Errors
-    ///
-    /// Will return `Err` if `target` is not Linux x86-64
-    pub fn is_linux_server(self) -> Result<bool, FtwError> {
-        if self.is(FtwTarget::LinuxX86_64) {
-            Ok(true)
-        } else {
-            Err(FtwError::UnsupportedTarget)
-        }
-    }
-
     pub fn is_android(self) -> bool {
         matches!(self, FtwTarget::AndroidLinuxAarch64 | FtwTarget::AndroidLinuxArmV7 | FtwTarget::AndroidLinuxX86 | FtwTarget::AndroidLinuxX86_64)
     }
@@ -106,6 +93,25 @@ impl ToExportName for FtwTarget {
     }
 }
 
+impl ToGdnlibFeatures for FtwTarget {
+    fn to_gdnlib_features(&self) -> GdnlibFeatures {
+        match self {
+            FtwTarget::AndroidLinuxAarch64 => "Android.arm64-v8a",
+            FtwTarget::AndroidLinuxArmV7 => "Android.armeabi-v7a",
+            FtwTarget::AndroidLinuxX86 => "Android.x86",
+            FtwTarget::AndroidLinuxX86_64 => "Android.x86_64",
+            FtwTarget::IosAarch64 => "iOS.arm64",
+            FtwTarget::LinuxX86 => "X11.32",
+            FtwTarget::LinuxX86_64 => "X11.64",
+            FtwTarget::MacOsX86_64 => "OSX.x86_64",
+            FtwTarget::MacOsAarch64 => "OSX.arm64",
+            FtwTarget::WindowsX86Gnu | FtwTarget::WindowsX86Msvc => "Windows.32",
+            FtwTarget::WindowsX86_64Gnu | FtwTarget::WindowsX86_64Msvc => "Windows.64",
+        }
+        .to_string()
+    }
+}
+
 impl ToAppExt for FtwTarget {
     fn to_app_ext(&self) -> AppExt {
         let s = self;
@@ -245,6 +251,28 @@ mod ftw_target_tests {
         }
     }
 
+    #[test]
+    fn test_to_gdnlib_features() {
+        let gdnlib_features_targets = [
+            ("Android.arm64-v8a", FtwTarget::AndroidLinuxAarch64),
+            ("Android.armeabi-v7a", FtwTarget::AndroidLinuxArmV7),
+            ("Android.x86", FtwTarget::AndroidLinuxX86),
+            ("Android.x86_64", FtwTarget::AndroidLinuxX86_64),
+            ("iOS.arm64", FtwTarget::IosAarch64),
+            ("X11.32", FtwTarget::LinuxX86),
+            ("X11.64", FtwTarget::LinuxX86_64),
+            ("OSX.x86_64", FtwTarget::MacOsX86_64),
+            ("OSX.arm64", FtwTarget::MacOsAarch64),
+            ("Windows.32", FtwTarget::WindowsX86Gnu),
+            ("Windows.32", FtwTarget::WindowsX86Msvc),
+            ("Windows.64", FtwTarget::WindowsX86_64Gnu),
+            ("Windows.64", FtwTarget::WindowsX86_64Msvc),
+        ];
+        for (gdnlib_features, target) in gdnlib_features_targets {
+            assert_eq!(gdnlib_features, target.to_gdnlib_features());
+        }
+    }
+
     #[test]
     fn test_to_app_ext() {
         let app_ext_targets = [
@@ -360,39 +388,6 @@ mod ftw_target_tests {
         Ok(())
     }
 
-    #[test]
-    fn test_is_linux_x86_64() -> Result<(), FtwError> {
-        let targets = [
-            (FtwTarget::AndroidLinuxAarch64),
-            (FtwTarget::AndroidLinuxArmV7),
-            (FtwTarget::AndroidLinuxX86),
-            (FtwTarget::AndroidLinuxX86_64),
-            (FtwTarget::IosAarch64),
-            (FtwTarget::LinuxX86),
-            (FtwTarget::LinuxX86_64),
-            (FtwTarget::MacOsX86_64),
-            (FtwTarget::MacOsAarch64),
-            (FtwTarget::WindowsX86Gnu),
-            (FtwTarget::WindowsX86Msvc),
-            (FtwTarget::WindowsX86Msvc),
-            (FtwTarget::WindowsX86_64Gnu),
-            (FtwTarget::WindowsX86_64Msvc),
-            (FtwTarget::WindowsX86_64Msvc),
-        ];
-        for target in targets {
-            if target == FtwTarget::LinuxX86_64 {
-                assert!(target.is_linux_server().unwrap());
-            } else {
-                let err = target.is_linux_server().unwrap_err();
-                match err {
-                    FtwError::UnsupportedTarget => assert!(true),
-                    _ => unreachable!(),
-                }
-            }
-        }
-        Ok(())
-    }
-
     #[test]
     fn test_fmt() {
         let cli_arg_targets = [
@@ -0,0 +1,293 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FtwTestStatus {
+    Passed,
+    Failed,
+    Ignored,
+}
+
+impl Display for FtwTestStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let message = match self {
+            FtwTestStatus::Passed => "passed",
+            FtwTestStatus::Failed => "failed",
+            FtwTestStatus::Ignored => "ignored",
+        };
+        write!(f, "{message}")
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwTestCase {
+    pub name: String,
+    pub status: FtwTestStatus,
+}
+
+/// The test cases of one test run, a run that exited with an error without
+/// reporting a failing test case gets a failing case of its own so that it
+/// cannot pass silently
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwTestSuite {
+    pub name: String,
+    pub cases: Vec<FtwTestCase>,
+}
+
+impl FtwTestSuite {
+    /// Parses the `test <name> ... ok` lines printed by `cargo test`
+    #[must_use]
+    pub fn from_cargo_output(output: &str, is_success: bool) -> Self {
+        let cases = output
+            .lines()
+            .filter_map(|line| {
+                let (name, result) = line.strip_prefix("test ")?.rsplit_once(" ... ")?;
+                let status = match result.trim() {
+                    "ok" => FtwTestStatus::Passed,
+                    "FAILED" => FtwTestStatus::Failed,
+                    result if result.starts_with("ignored") => FtwTestStatus::Ignored,
+                    _ => return None,
+                };
+                Some(FtwTestCase {
+                    name: name.to_string(),
+                    status,
+                })
+            })
+            .collect();
+        FtwTestSuite::new("cargo", cases, is_success)
+    }
+
+    /// Parses the TAP lines (`ok 1 - name`, `not ok 2 - name` and
+    /// `ok 3 - name # SKIP`) printed by the godot test runner
+    #[must_use]
+    pub fn from_godot_output(output: &str, is_success: bool) -> Self {
+        let cases = output
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                let (is_ok, rest) = match line.strip_prefix("not ok") {
+                    Some(rest) => (false, rest),
+                    None => (true, line.strip_prefix("ok")?),
+                };
+                if !rest.is_empty() && !rest.starts_with(' ') {
+                    return None;
+                }
+                let rest = rest.trim_start();
+                let number: String = rest.chars().take_while(char::is_ascii_digit).collect();
+                let rest = rest[number.len()..].trim_start();
+                let description = rest.strip_prefix('-').unwrap_or(rest).trim_start();
+                let (name, directive) = description
+                    .split_once(" # ")
+                    .or_else(|| description.strip_prefix("# ").map(|d| ("", d)))
+                    .unwrap_or((description, ""));
+                let is_skipped = directive.to_uppercase().starts_with("SKIP");
+                let status = match (is_ok, is_skipped) {
+                    (_, true) => FtwTestStatus::Ignored,
+                    (true, false) => FtwTestStatus::Passed,
+                    (false, false) => FtwTestStatus::Failed,
+                };
+                let name = match name.trim() {
+                    "" => format!("test {number}"),
+                    name => name.to_string(),
+                };
+                Some(FtwTestCase { name, status })
+            })
+            .collect();
+        FtwTestSuite::new("godot", cases, is_success)
+    }
+
+    fn new(name: &str, mut cases: Vec<FtwTestCase>, is_success: bool) -> Self {
+        let has_failures = cases
+            .iter()
+            .any(|case| case.status == FtwTestStatus::Failed);
+        if !is_success && !has_failures {
+            cases.push(FtwTestCase {
+                name: format!("{name} exited with an error"),
+                status: FtwTestStatus::Failed,
+            });
+        }
+        FtwTestSuite {
+            name: name.to_string(),
+            cases,
+        }
+    }
+
+    #[must_use]
+    pub fn count(&self, status: FtwTestStatus) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| case.status == status)
+            .count()
+    }
+}
+
+/// The unified report of the rust and godot tests
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FtwTestReport {
+    pub suites: Vec<FtwTestSuite>,
+}
+
+impl FtwTestReport {
+    #[must_use]
+    pub fn count(&self, status: FtwTestStatus) -> usize {
+        self.suites.iter().map(|suite| suite.count(status)).sum()
+    }
+
+    #[must_use]
+    pub fn is_passing(&self) -> bool {
+        self.count(FtwTestStatus::Failed) == 0
+    }
+
+    #[must_use]
+    pub fn to_junit_xml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites tests=\"{}\" failures=\"{}\" skipped=\"{}\">\n",
+            self.suites
+                .iter()
+                .map(|suite| suite.cases.len())
+                .sum::<usize>(),
+            self.count(FtwTestStatus::Failed),
+            self.count(FtwTestStatus::Ignored)
+        ));
+        for suite in &self.suites {
+            let suite_name = escape_xml(&suite.name);
+            xml.push_str(&format!(
+                "  <testsuite name=\"{suite_name}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\">\n",
+                suite.cases.len(),
+                suite.count(FtwTestStatus::Failed),
+                suite.count(FtwTestStatus::Ignored)
+            ));
+            for case in &suite.cases {
+                let case_name = escape_xml(&case.name);
+                let testcase =
+                    format!("    <testcase name=\"{case_name}\" classname=\"{suite_name}\"");
+                match case.status {
+                    FtwTestStatus::Passed => xml.push_str(&format!("{testcase}/>\n")),
+                    FtwTestStatus::Failed => xml.push_str(&format!(
+                        "{testcase}>\n      <failure message=\"failed\"/>\n    </testcase>\n"
+                    )),
+                    FtwTestStatus::Ignored => {
+                        xml.push_str(&format!("{testcase}>\n      <skipped/>\n    </testcase>\n"))
+                    }
+                }
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+impl Display for FtwTestReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let lines: Vec<String> = self
+            .suites
+            .iter()
+            .flat_map(|suite| {
+                let name = &suite.name;
+                let passed = suite.count(FtwTestStatus::Passed);
+                let failed = suite.count(FtwTestStatus::Failed);
+                let ignored = suite.count(FtwTestStatus::Ignored);
+                let summary =
+                    format!("{name}: {passed} passed, {failed} failed, {ignored} ignored");
+                let failures = suite
+                    .cases
+                    .iter()
+                    .filter(|case| case.status == FtwTestStatus::Failed)
+                    .map(|case| format!("  [{}] {}", case.status, case.name));
+                std::iter::once(summary).chain(failures)
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod ftw_test_report_tests {
+    use super::*;
+
+    const CARGO_OUTPUT: &str = r#"
+running 3 tests
+test tests::test_speed ... ok
+test tests::test_health ... FAILED
+test tests::test_slow ... ignored, too slow
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out
+"#;
+
+    const GODOT_OUTPUT: &str = r#"Godot Engine v3.5.3.stable.official
+1..4
+ok 1 - Player jumps
+not ok 2 - Enemy <Goblin> attacks
+ok 3 - Saves the game # SKIP no user dir
+ok 4
+"#;
+
+    #[test]
+    fn test_from_cargo_output() {
+        let suite = FtwTestSuite::from_cargo_output(CARGO_OUTPUT, false);
+        assert_eq!(3, suite.cases.len());
+        assert_eq!(1, suite.count(FtwTestStatus::Passed));
+        assert_eq!(1, suite.count(FtwTestStatus::Failed));
+        assert_eq!(1, suite.count(FtwTestStatus::Ignored));
+        let suite = FtwTestSuite::from_cargo_output("error: could not compile", false);
+        assert_eq!(
+            vec![FtwTestCase {
+                name: "cargo exited with an error".to_string(),
+                status: FtwTestStatus::Failed
+            }],
+            suite.cases
+        );
+    }
+
+    #[test]
+    fn test_from_godot_output() {
+        let suite = FtwTestSuite::from_godot_output(GODOT_OUTPUT, false);
+        let cases: Vec<(&str, FtwTestStatus)> = suite
+            .cases
+            .iter()
+            .map(|case| (case.name.as_str(), case.status))
+            .collect();
+        assert_eq!(
+            vec![
+                ("Player jumps", FtwTestStatus::Passed),
+                ("Enemy <Goblin> attacks", FtwTestStatus::Failed),
+                ("Saves the game", FtwTestStatus::Ignored),
+                ("test 4", FtwTestStatus::Passed),
+            ],
+            cases
+        );
+        let suite = FtwTestSuite::from_godot_output("Godot Engine", true);
+        assert!(suite.cases.is_empty());
+    }
+
+    #[test]
+    fn test_to_junit_xml() {
+        let report = FtwTestReport {
+            suites: vec![
+                FtwTestSuite::from_cargo_output(CARGO_OUTPUT, false),
+                FtwTestSuite::from_godot_output(GODOT_OUTPUT, false),
+            ],
+        };
+        assert!(!report.is_passing());
+        let xml = report.to_junit_xml();
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites tests=\"7\" failures=\"2\" skipped=\"2\">\n"));
+        assert!(xml.contains("  <testsuite name=\"cargo\" tests=\"3\" failures=\"1\" skipped=\"1\">\n    <testcase name=\"tests::test_speed\" classname=\"cargo\"/>\n"));
+        assert!(xml.contains("    <testcase name=\"Enemy &lt;Goblin&gt; attacks\" classname=\"godot\">\n      <failure message=\"failed\"/>\n    </testcase>\n"));
+        assert!(xml.contains("    <testcase name=\"Saves the game\" classname=\"godot\">\n      <skipped/>\n    </testcase>\n"));
+        assert!(xml.ends_with("  </testsuite>\n</testsuites>\n"));
+        assert_eq!(
+            "cargo: 1 passed, 1 failed, 1 ignored\n  [failed] tests::test_health\ngodot: 2 passed, 1 failed, 1 ignored\n  [failed] Enemy <Goblin> attacks",
+            report.to_string()
+        );
+    }
+}
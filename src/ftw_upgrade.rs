@@ -0,0 +1,235 @@
+use crate::ftw_check::TAG_FILE;
+use crate::ftw_error::FtwError;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs::{create_dir_all, read, remove_file, write};
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// The tag of the projects created before the template started writing `.tag`
+pub const UNTAGGED_TEMPLATE_TAG: &str = "v1.2.0";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FtwUpgradeStatus {
+    Added,
+    Updated,
+    Merged,
+    Removed,
+    Conflict,
+}
+
+impl Display for FtwUpgradeStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let message = match self {
+            FtwUpgradeStatus::Added => "added",
+            FtwUpgradeStatus::Updated => "updated",
+            FtwUpgradeStatus::Merged => "merged",
+            FtwUpgradeStatus::Removed => "removed",
+            FtwUpgradeStatus::Conflict => "conflict",
+        };
+        write!(f, "{message}")
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FtwUpgradeChange {
+    pub path: String,
+    pub status: FtwUpgradeStatus,
+}
+
+/// Three-way merges the differences between the renders of the old and the
+/// new template versions into a project, the project changes are kept and
+/// the conflicting files get conflict markers
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FtwUpgrade {
+    pub changes: Vec<FtwUpgradeChange>,
+}
+
+impl FtwUpgrade {
+    fn get_files(dir: &Path) -> Result<BTreeSet<String>, FtwError> {
+        let mut files = BTreeSet::new();
+        let entries = WalkDir::new(dir)
+            .into_iter()
+            .filter_entry(|entry| entry.file_name() != ".git");
+        for entry in entries {
+            let entry = entry?;
+            if entry.file_type().is_file() {
+                let path = entry
+                    .path()
+                    .strip_prefix(dir)
+                    .map_err(|_| FtwError::PathError)?;
+                let path = path.to_str().ok_or(FtwError::StringConversionError)?;
+                files.insert(path.replace('\\', "/"));
+            }
+        }
+        files.remove(TAG_FILE);
+        Ok(files)
+    }
+
+    fn read(dir: &Path, path: &str) -> Option<Vec<u8>> {
+        read(dir.join(path)).ok()
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the renders or the project files cannot be read or written
+    pub fn merge(old_dir: &Path, new_dir: &Path, project_dir: &Path) -> Result<Self, FtwError> {
+        let old_files = FtwUpgrade::get_files(old_dir)?;
+        let new_files = FtwUpgrade::get_files(new_dir)?;
+        let mut changes = vec![];
+        for path in old_files.union(&new_files) {
+            let old = FtwUpgrade::read(old_dir, path);
+            let new = FtwUpgrade::read(new_dir, path);
+            let ours = FtwUpgrade::read(project_dir, path);
+            if old == new || ours == new {
+                continue;
+            }
+            let project_path = project_dir.join(path);
+            let status = match (old, new, ours) {
+                (Some(old), None, Some(ours)) if old == ours => {
+                    remove_file(&project_path)?;
+                    FtwUpgradeStatus::Removed
+                }
+                (Some(_), None, _) | (Some(_), Some(_), None) => FtwUpgradeStatus::Conflict,
+                (None, Some(new), None) => {
+                    create_dir_all(project_path.parent().ok_or(FtwError::PathError)?)?;
+                    write(&project_path, new)?;
+                    FtwUpgradeStatus::Added
+                }
+                (Some(old), Some(new), Some(ours)) if old == ours => {
+                    write(&project_path, new)?;
+                    FtwUpgradeStatus::Updated
+                }
+                (old, Some(new), Some(ours)) => {
+                    let old = old.unwrap_or_default();
+                    match (
+                        String::from_utf8(old),
+                        String::from_utf8(new),
+                        String::from_utf8(ours),
+                    ) {
+                        (Ok(old), Ok(new), Ok(ours)) => match diffy::merge(&old, &ours, &new) {
+                            Ok(merged) => {
+                                write(&project_path, merged)?;
+                                FtwUpgradeStatus::Merged
+                            }
+                            Err(conflicted) => {
+                                write(&project_path, conflicted)?;
+                                FtwUpgradeStatus::Conflict
+                            }
+                        },
+                        _ => FtwUpgradeStatus::Conflict,
+                    }
+                }
+                (None, None, _) => continue,
+            };
+            changes.push(FtwUpgradeChange {
+                path: path.to_string(),
+                status,
+            });
+        }
+        Ok(FtwUpgrade { changes })
+    }
+
+    #[must_use]
+    pub fn get_conflicts(&self) -> Vec<String> {
+        self.changes
+            .iter()
+            .filter(|change| change.status == FtwUpgradeStatus::Conflict)
+            .map(|change| change.path.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod ftw_upgrade_tests {
+    use super::*;
+    use std::env;
+    use std::fs::{read_to_string, remove_dir_all};
+
+    fn write_files(dir: &Path, files: &[(&str, &str)]) -> Result<(), FtwError> {
+        for (path, contents) in files {
+            let path = dir.join(path);
+            create_dir_all(path.parent().ok_or(FtwError::PathError)?)?;
+            write(path, contents)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge() -> Result<(), FtwError> {
+        let dir = env::temp_dir().join(format!("ftw-upgrade-{}", std::process::id()));
+        let (old_dir, new_dir, project_dir) =
+            (dir.join("old"), dir.join("new"), dir.join("project"));
+        write_files(
+            &old_dir,
+            &[
+                (".tag", "v1.4.0"),
+                ("Makefile", "build:\n\tcargo build\n"),
+                ("README.md", "# game\n"),
+                ("godot/project.godot", "a\nb\nc\nd\ne\n"),
+                ("rust/Cargo.toml", "[package]\nversion = \"0.1.0\"\n"),
+                ("sh/build.sh", "make\n"),
+            ],
+        )?;
+        write_files(
+            &new_dir,
+            &[
+                (".tag", "v1.5.0"),
+                ("Makefile.toml", "[tasks.build]\n"),
+                ("README.md", "# game\n"),
+                ("godot/project.godot", "a\nb\nc\nd\ne\nf\n"),
+                ("rust/Cargo.toml", "[package]\nversion = \"0.2.0\"\n"),
+                ("sh/build.sh", "cargo make\n"),
+            ],
+        )?;
+        write_files(
+            &project_dir,
+            &[
+                (".tag", "v1.4.0"),
+                ("Makefile", "build:\n\tcargo build\n"),
+                ("README.md", "# my game\n"),
+                ("godot/project.godot", "A\nb\nc\nd\ne\n"),
+                ("rust/Cargo.toml", "[package]\nversion = \"1.0.0\"\n"),
+            ],
+        )?;
+        let upgrade = FtwUpgrade::merge(&old_dir, &new_dir, &project_dir)?;
+        let changes: Vec<(&str, FtwUpgradeStatus)> = upgrade
+            .changes
+            .iter()
+            .map(|change| (change.path.as_str(), change.status))
+            .collect();
+        assert_eq!(
+            vec![
+                ("Makefile", FtwUpgradeStatus::Removed),
+                ("Makefile.toml", FtwUpgradeStatus::Added),
+                ("godot/project.godot", FtwUpgradeStatus::Merged),
+                ("rust/Cargo.toml", FtwUpgradeStatus::Conflict),
+                ("sh/build.sh", FtwUpgradeStatus::Conflict),
+            ],
+            changes
+        );
+        assert_eq!(
+            vec!["rust/Cargo.toml".to_string(), "sh/build.sh".to_string()],
+            upgrade.get_conflicts()
+        );
+        assert!(!project_dir.join("Makefile").exists());
+        assert_eq!(
+            "[tasks.build]\n",
+            read_to_string(project_dir.join("Makefile.toml"))?
+        );
+        assert_eq!(
+            "A\nb\nc\nd\ne\nf\n",
+            read_to_string(project_dir.join("godot/project.godot"))?
+        );
+        assert!(read_to_string(project_dir.join("rust/Cargo.toml"))?.contains("<<<<<<<"));
+        assert!(!project_dir.join("sh/build.sh").exists());
+        assert_eq!(
+            "# my game\n",
+            read_to_string(project_dir.join("README.md"))?
+        );
+        assert_eq!("v1.4.0", read_to_string(project_dir.join(".tag"))?);
+        remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
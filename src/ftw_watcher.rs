@@ -0,0 +1,162 @@
+use crate::ftw_error::FtwError;
+use crate::ftw_fingerprint::FtwFingerprint;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+use walkdir::WalkDir;
+
+/// Watches files and directories by polling their modification times, the
+/// hidden and backup files that editors write next to the sources are ignored
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FtwWatcher {
+    pub paths: Vec<PathBuf>,
+    snapshot: BTreeMap<PathBuf, u128>,
+}
+
+impl FtwWatcher {
+    /// # Errors
+    ///
+    /// Will return `Err` if the watched files cannot be read
+    pub fn new(paths: Vec<PathBuf>) -> Result<Self, FtwError> {
+        let snapshot = FtwWatcher::get_snapshot(&paths)?;
+        Ok(FtwWatcher { paths, snapshot })
+    }
+
+    /// The `Cargo.toml` of the workspace at `root` plus the `Cargo.toml` and
+    /// `src/` of every workspace member (`rust/` if the workspace cannot be
+    /// read)
+    #[must_use]
+    pub fn get_project_paths(root: &Path) -> Vec<PathBuf> {
+        let members = fs::read_to_string(root.join("Cargo.toml"))
+            .ok()
+            .and_then(|contents| contents.parse::<toml::Table>().ok())
+            .and_then(|cargo_toml| {
+                let members = cargo_toml.get("workspace")?.get("members")?.as_array()?;
+                Some(
+                    members
+                        .iter()
+                        .filter_map(|member| member.as_str().map(ToString::to_string))
+                        .collect::<Vec<String>>(),
+                )
+            })
+            .filter(|members| !members.is_empty())
+            .unwrap_or_else(|| vec!["rust".to_string()]);
+        let mut paths = vec![root.join("Cargo.toml")];
+        for member in members {
+            paths.push(root.join(&member).join("Cargo.toml"));
+            paths.push(root.join(&member).join("src"));
+        }
+        paths
+    }
+
+    fn is_ignored(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|file_name| file_name.to_str())
+            .is_some_and(|file_name| file_name.starts_with('.') || file_name.ends_with('~'))
+    }
+
+    fn get_snapshot(paths: &[PathBuf]) -> Result<BTreeMap<PathBuf, u128>, FtwError> {
+        let mut snapshot = BTreeMap::new();
+        for path in paths.iter().filter(|path| path.exists()) {
+            for entry in WalkDir::new(path) {
+                let entry = entry?;
+                if entry.file_type().is_file() && !FtwWatcher::is_ignored(entry.path()) {
+                    let modified = FtwFingerprint::get_modified(entry.path())?;
+                    snapshot.insert(entry.path().to_path_buf(), modified);
+                }
+            }
+        }
+        Ok(snapshot)
+    }
+
+    /// Returns the files that were added, modified or removed since the last poll
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the watched files cannot be read
+    pub fn poll(&mut self) -> Result<Vec<PathBuf>, FtwError> {
+        let snapshot = FtwWatcher::get_snapshot(&self.paths)?;
+        let modified = snapshot
+            .iter()
+            .filter(|(path, modified)| self.snapshot.get(*path) != Some(*modified))
+            .map(|(path, _)| path.clone());
+        let removed = self
+            .snapshot
+            .keys()
+            .filter(|path| !snapshot.contains_key(*path))
+            .cloned();
+        let changes = modified.chain(removed).collect();
+        self.snapshot = snapshot;
+        Ok(changes)
+    }
+
+    /// Blocks until files changed and no other change happened for `debounce`,
+    /// so a burst of saves is returned as a single batch of changes
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the watched files cannot be read
+    pub fn wait_for_changes(
+        &mut self,
+        interval: Duration,
+        debounce: Duration,
+    ) -> Result<Vec<PathBuf>, FtwError> {
+        let mut changes: Vec<PathBuf> = vec![];
+        let mut last_change = Instant::now();
+        loop {
+            thread::sleep(interval);
+            let new_changes = self.poll()?;
+            if !new_changes.is_empty() {
+                last_change = Instant::now();
+                for change in new_changes {
+                    if !changes.contains(&change) {
+                        changes.push(change);
+                    }
+                }
+            } else if !changes.is_empty() && last_change.elapsed() >= debounce {
+                return Ok(changes);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod ftw_watcher_tests {
+    use super::*;
+    use std::env;
+    use std::fs::File;
+    use std::time::SystemTime;
+
+    #[test]
+    fn test_poll() -> Result<(), FtwError> {
+        let dir = env::temp_dir().join(format!("ftw-watcher-{}", std::process::id()));
+        let src = dir.join("src");
+        fs::create_dir_all(&src)?;
+        let lib_rs = src.join("lib.rs");
+        fs::write(&lib_rs, "mod game;")?;
+        let mut watcher = FtwWatcher::new(vec![dir.join("Cargo.toml"), src.clone()])?;
+        assert!(watcher.poll()?.is_empty());
+        let game_rs = src.join("game.rs");
+        fs::write(&game_rs, "")?;
+        fs::write(src.join(".game.rs.swp"), "")?;
+        fs::write(src.join("game.rs~"), "")?;
+        assert_eq!(vec![game_rs.clone()], watcher.poll()?);
+        File::options()
+            .write(true)
+            .open(&lib_rs)?
+            .set_modified(SystemTime::UNIX_EPOCH)?;
+        assert_eq!(vec![lib_rs], watcher.poll()?);
+        fs::remove_file(&game_rs)?;
+        assert_eq!(vec![game_rs], watcher.poll()?);
+        let cargo_toml = dir.join("Cargo.toml");
+        fs::write(&cargo_toml, "[workspace]")?;
+        assert_eq!(
+            vec![cargo_toml.clone()],
+            watcher.wait_for_changes(Duration::ZERO, Duration::ZERO)?
+        );
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
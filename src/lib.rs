@@ -1,14 +1,33 @@
+pub mod ftw_bindings;
 pub mod ftw_build_type;
+pub mod ftw_check;
 pub mod ftw_command;
 pub mod ftw_compiler;
 pub mod ftw_configuration;
+pub mod ftw_container;
 pub mod ftw_error;
+pub mod ftw_event;
+pub mod ftw_fingerprint;
+pub mod ftw_gdnlib;
+pub mod ftw_instance;
+pub mod ftw_keystore;
 pub mod ftw_machine_type;
+pub mod ftw_manifest;
 pub mod ftw_node_type;
+pub mod ftw_package;
+pub mod ftw_presets;
+pub mod ftw_project;
+pub mod ftw_property;
+pub mod ftw_run_options;
+pub mod ftw_scene;
+pub mod ftw_signal;
 pub mod ftw_success;
 pub mod ftw_tag;
 pub mod ftw_target;
 pub mod ftw_template;
+pub mod ftw_test_report;
+pub mod ftw_upgrade;
+pub mod ftw_watcher;
 pub mod run_command;
 pub mod test_util;
 pub mod traits;
@@ -1,23 +1,11 @@
-mod ftw_build_type;
-mod ftw_command;
-mod ftw_compiler;
-mod ftw_configuration;
-mod ftw_error;
-mod ftw_machine_type;
-mod ftw_node_type;
-mod ftw_success;
-mod ftw_tag;
-mod ftw_target;
-mod ftw_template;
-mod run_command;
-mod test_util;
-mod traits;
-mod type_alias;
-mod util;
-
-use crate::ftw_command::FtwCommand;
-use crate::traits::{Processor, ToMessage};
-use clap::{arg, command, crate_name, ArgMatches, Command};
+use clap::{arg, command, crate_name, Arg, ArgMatches, Command};
+use ftw::ftw_build_type::FtwBuildType;
+use ftw::ftw_command::FtwCommand;
+use ftw::ftw_error::FtwError;
+use ftw::ftw_instance::FtwInstance;
+use ftw::ftw_run_options::FtwRunOptions;
+use ftw::traits::{Processor, ToMessage};
+use ftw::util;
 use itertools::Itertools;
 use std::env;
 
@@ -25,6 +13,8 @@ use std::env;
 fn main() -> Result<(), ()> {
     let matches = get_clap_command().get_matches();
     let command = parse_matches(&matches);
+    let command = enter_project_directory(get_project_directory(&matches), command)
+        .map_err(|ftw_error| eprintln!("{}", ftw_error.to_message()))?;
     command
         .process()
         .map(|ftw_success| println!("{}", ftw_success.to_message()))
@@ -40,6 +30,10 @@ fn get_clap_command() -> Command {
 
 {all-args}{after-help}"#,
         )
+        .arg(
+            arg!(-C --project <dir> "run as if ftw was started in <dir> instead of the current directory")
+                .global(true),
+        )
         .subcommand(
             Command::new("new")
                 .about("create a new godot-rust project directory")
@@ -51,17 +45,62 @@ fn get_clap_command() -> Command {
             Command::new("class")
                 .about("create a new class to be used by a node")
                 .arg(arg!(<class_name> "the name of this class"))
-                .arg(arg!([node_type] "the type of the node that this class inherits from")),
+                .arg(arg!([node_type] "the type of the node that this class inherits from"))
+                .arg(arg!(--prop <prop> ... "an exported property as name:type=default, e.g. speed:f32=200 or health:i64"))
+                .arg(arg!(--signal <signal> ... "a signal as name:type,type..., e.g. died or hit:i64"))
+                .arg(arg!(--"extends-scene" <scene> "the scene to inherit from, the node type is the one of its root node").conflicts_with("node_type"))
+                .arg(arg!(--child <child> ... "the node type of a child node to add to the scene, e.g. Sprite")),
         )
         .subcommand(
             Command::new("singleton")
                 .about("create a singleton (autoloaded) class")
                 .arg(arg!(<class_name> "the name of this class")),
         )
+        .subcommand(
+            Command::new("attach")
+                .about("create a class for an existing scene and attach it to the root node")
+                .arg(arg!(<scene> "the tscn file of the scene")),
+        )
+        .subcommand(
+            Command::new("bindings")
+                .about("generate typed accessors of the child nodes of the scene of a class")
+                .arg(arg!(<class_name> "the name of the class")),
+        )
+        .subcommand(
+            Command::new("resource")
+                .about("create a new resource class with exported properties")
+                .arg(arg!(<class_name> "the name of this class"))
+                .arg(arg!(--prop <prop> ... "an exported property as name:type, e.g. damage:i32 or icon:Texture")),
+        )
         .subcommand(
             Command::new("run")
                 .about("run a debug version of the game")
-                .arg(arg!([machine_type] "either desktop or server")),
+                .arg(arg!([machine_type] "either desktop or server"))
+                .arg(arg!(--release "run the game against a release-built library"))
+                .arg(
+                    Arg::new("exported")
+                        .long("exported")
+                        .num_args(0..=2)
+                        .value_names(["target", "build_type"])
+                        .help("export then run the exported game for the current platform"),
+                )
+                .arg(arg!(--server "also run a server, each instance has its output prefixed").conflicts_with("exported"))
+                .arg(
+                    arg!(--clients <clients> "the number of desktop clients to run next to the server")
+                        .value_parser(clap::value_parser!(usize))
+                        .conflicts_with("exported"),
+                )
+                .arg(arg!(--scene <scene> "a tscn file or a class name to run instead of the main scene"))
+                .arg(arg!(--verbose "enable verbose stdout mode"))
+                .arg(arg!(--"debug-collisions" "show collision shapes when running the scene"))
+                .arg(arg!(--"debug-navigation" "show navigation polygons when running the scene"))
+                .arg(
+                    Arg::new("args")
+                        .num_args(1..)
+                        .last(true)
+                        .allow_hyphen_values(true)
+                        .help("the arguments after -- are passed to the game"),
+                ),
         )
         .subcommand(
             Command::new("build")
@@ -75,9 +114,108 @@ fn get_clap_command() -> Command {
                 .arg(arg!([targets] "target platform to export"))
                 .arg(arg!([build_type] "either a debug or release")),
         )
+        .subcommand(
+            Command::new("package")
+                .about("package the exported game into a distributable archive")
+                .arg(arg!([targets] "target platforms to package, separated by ','"))
+                .arg(arg!([build_type] "either a debug or release")),
+        )
+        .subcommand(
+            Command::new("presets")
+                .about("manage the export presets of the game")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("sync")
+                        .about("add the missing export presets and report the drifted ones"),
+                ),
+        )
+        .subcommand(
+            Command::new("android")
+                .about("manage the android signing of the game")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("keystore")
+                        .about("create a keystore and configure it in the android export presets")
+                        .arg(arg!([build_type] "either a debug or release")),
+                ),
+        )
+        .subcommand(
+            Command::new("watch")
+                .about("rebuild the library whenever the rust sources change")
+                .arg(arg!([target] "target platform to build"))
+                .arg(arg!([build_type] "either a debug or release"))
+                .arg(arg!(--run "run the game and restart it after every rebuild")),
+        )
+        .subcommand(
+            Command::new("test")
+                .about("run the rust tests then the godot tests")
+                .arg(arg!(--runner <runner> "the test runner scene or script, overrides test-runner in .ftw"))
+                .arg(arg!(--junit <path> "write a JUnit XML report of the tests")),
+        )
+        .subcommand(
+            Command::new("check")
+                .about("list the missing required paths of the project")
+                .arg(arg!(--fix "recreate the missing target directories and .gitkeep files")),
+        )
+        .subcommand(
+            Command::new("upgrade")
+                .about("upgrade the project to another version of its template")
+                .arg(arg!([tag] "it can be any tag defined in the template or 'latest'"))
+                .arg(arg!(--template <template> "the template the project was created from")),
+        )
+        .subcommand(
+            Command::new("artifacts")
+                .about("list and verify the built libraries and exported games"),
+        )
         .subcommand(Command::new("clean").about("cleans the project from excess artifacts"))
 }
 
+fn get_project_directory(matches: &ArgMatches) -> Option<String> {
+    matches
+        .subcommand()
+        .and_then(|(_, args)| args.get_one::<String>("project"))
+        .or_else(|| matches.get_one::<String>("project"))
+        .cloned()
+}
+
+/// Changes the current directory to `project_directory` (if any) and then to
+/// the nearest project root above it, except for `ftw new` which creates the
+/// project inside the current directory, the class of `ftw class` and `ftw
+/// singleton` goes into the subdirectory it was started in (e.g. `ftw class
+/// Goblin` inside `rust/src/enemies` creates `enemies/Goblin`) and the paths
+/// given on the command line stay relative to the directory it was started in
+#[cfg(not(tarpaulin_include))]
+fn enter_project_directory(
+    project_directory: Option<String>,
+    command: FtwCommand,
+) -> Result<FtwCommand, FtwError> {
+    let started_dir = env::current_dir()?;
+    if let Some(project_directory) = project_directory {
+        env::set_current_dir(&project_directory)
+            .map_err(|_| FtwError::MissingProjectDirectory(project_directory))?;
+    }
+    if matches!(command, FtwCommand::New { .. }) {
+        return Ok(command);
+    }
+    let current_dir = env::current_dir()?;
+    let project_root = FtwCommand::find_project_root(&current_dir);
+    let command = command.with_paths_relative_to(
+        &started_dir,
+        project_root.as_deref().unwrap_or(&current_dir),
+    );
+    match project_root {
+        Some(project_root) => {
+            env::set_current_dir(&project_root)?;
+            let relative_dir = current_dir
+                .strip_prefix(&project_root)
+                .unwrap_or(&current_dir);
+            let directories = util::get_class_directories_from_path(relative_dir);
+            Ok(command.with_class_directories(&directories))
+        }
+        None => Ok(command),
+    }
+}
+
 fn parse_matches(matches: &ArgMatches) -> FtwCommand {
     match matches.subcommand() {
         Some(("new", args)) => {
@@ -111,9 +249,26 @@ fn parse_matches(matches: &ArgMatches) -> FtwCommand {
                 .unwrap_or(&String::from("Node"))
                 .parse()
                 .unwrap_or_default();
+            let properties = args
+                .get_many("prop")
+                .map(|properties| properties.cloned().collect())
+                .unwrap_or_default();
+            let signals = args
+                .get_many("signal")
+                .map(|signals| signals.cloned().collect())
+                .unwrap_or_default();
+            let extends_scene = args.get_one("extends-scene").cloned();
+            let children = args
+                .get_many("child")
+                .map(|children| children.cloned().collect())
+                .unwrap_or_default();
             FtwCommand::Class {
                 class_name,
                 node_type,
+                properties,
+                signals,
+                extends_scene,
+                children,
             }
         }
         Some(("singleton", args)) => {
@@ -123,13 +278,80 @@ fn parse_matches(matches: &ArgMatches) -> FtwCommand {
                 .to_string();
             FtwCommand::Singleton { class_name }
         }
+        Some(("attach", args)) => {
+            let scene = args
+                .get_one("scene")
+                .unwrap_or(&String::from("scenes/Main.tscn"))
+                .to_string();
+            FtwCommand::Attach { scene }
+        }
+        Some(("bindings", args)) => {
+            let class_name = args
+                .get_one("class_name")
+                .unwrap_or(&String::from("MyClass"))
+                .to_string();
+            FtwCommand::Bindings { class_name }
+        }
+        Some(("resource", args)) => {
+            let class_name = args
+                .get_one("class_name")
+                .unwrap_or(&String::from("MyResource"))
+                .to_string();
+            let properties = args
+                .get_many("prop")
+                .map(|properties| properties.cloned().collect())
+                .unwrap_or_default();
+            FtwCommand::Resource {
+                class_name,
+                properties,
+            }
+        }
         Some(("run", args)) => {
             let machine_type = args
                 .get_one("machine_type")
                 .unwrap_or(&String::from("desktop"))
                 .parse()
                 .unwrap_or_default();
-            FtwCommand::Run { machine_type }
+            let release_build_type = if args.get_flag("release") {
+                FtwBuildType::Release
+            } else {
+                FtwBuildType::Debug
+            };
+            let exported_args: Vec<&String> = args
+                .get_many("exported")
+                .map(Iterator::collect)
+                .unwrap_or_default();
+            let build_type = exported_args
+                .iter()
+                .find_map(|arg| arg.parse().ok())
+                .unwrap_or(release_build_type);
+            let exported = args.contains_id("exported").then(|| {
+                exported_args
+                    .iter()
+                    .find_map(|arg| arg.parse().ok())
+                    .unwrap_or_else(|| util::get_current_platform().parse().unwrap_or_default())
+            });
+            let options = FtwRunOptions {
+                scene: args.get_one("scene").cloned(),
+                verbose: args.get_flag("verbose"),
+                debug_collisions: args.get_flag("debug-collisions"),
+                debug_navigation: args.get_flag("debug-navigation"),
+                user_args: args
+                    .get_many("args")
+                    .map(|user_args| user_args.cloned().collect())
+                    .unwrap_or_default(),
+            };
+            let instances = FtwInstance::get_instances(
+                args.get_flag("server"),
+                args.get_one("clients").copied().unwrap_or_default(),
+            );
+            FtwCommand::Run {
+                machine_type,
+                build_type,
+                exported,
+                options,
+                instances,
+            }
         }
         Some(("build", args)) => {
             let current_platform = util::get_current_platform();
@@ -171,6 +393,83 @@ fn parse_matches(matches: &ArgMatches) -> FtwCommand {
                 build_type,
             }
         }
+        Some(("package", args)) => {
+            let current_platform = util::get_current_platform();
+            let targets = args
+                .get_one("targets")
+                .unwrap_or(&current_platform)
+                .split(',')
+                .map(|t| t.parse().unwrap_or_default())
+                .sorted()
+                .dedup()
+                .collect();
+            let build_type = args
+                .get_one("build_type")
+                .unwrap_or(&String::from("debug"))
+                .parse()
+                .unwrap_or_default();
+            FtwCommand::Package {
+                targets,
+                build_type,
+            }
+        }
+        Some(("presets", args)) => match args.subcommand() {
+            Some(("sync", _args)) => FtwCommand::PresetsSync,
+            _ => unreachable!(),
+        },
+        Some(("android", args)) => match args.subcommand() {
+            Some(("keystore", args)) => {
+                let build_type = args
+                    .get_one("build_type")
+                    .unwrap_or(&String::from("debug"))
+                    .parse()
+                    .unwrap_or_default();
+                FtwCommand::AndroidKeystore { build_type }
+            }
+            _ => unreachable!(),
+        },
+        Some(("watch", args)) => {
+            let current_platform = util::get_current_platform();
+            let target = args
+                .get_one("target")
+                .unwrap_or(&current_platform)
+                .parse()
+                .unwrap_or_default();
+            let build_type = args
+                .get_one("build_type")
+                .unwrap_or(&String::from("debug"))
+                .parse()
+                .unwrap_or_default();
+            let run = args.get_flag("run");
+            FtwCommand::Watch {
+                target,
+                build_type,
+                run,
+            }
+        }
+        Some(("test", args)) => {
+            let runner = args.get_one("runner").cloned();
+            let junit = args.get_one("junit").cloned();
+            FtwCommand::Test { runner, junit }
+        }
+        Some(("check", args)) => {
+            let fix = args.get_flag("fix");
+            FtwCommand::Check { fix }
+        }
+        Some(("upgrade", args)) => {
+            let tag = args
+                .get_one("tag")
+                .unwrap_or(&String::from("latest"))
+                .parse()
+                .unwrap_or_default();
+            let template = args
+                .get_one("template")
+                .unwrap_or(&String::from("default"))
+                .parse()
+                .unwrap_or_default();
+            FtwCommand::Upgrade { template, tag }
+        }
+        Some(("artifacts", _args)) => FtwCommand::Artifacts,
         Some(("clean", _args)) => FtwCommand::Clean,
         _ => unreachable!(),
     }
@@ -179,14 +478,13 @@ fn parse_matches(matches: &ArgMatches) -> FtwCommand {
 #[cfg(test)]
 mod main_tests {
     use super::*;
-    use crate::ftw_build_type::FtwBuildType;
-    use crate::ftw_command::FtwCommand;
-    use crate::ftw_machine_type::FtwMachineType;
-    use crate::ftw_node_type::FtwNodeType;
-    use crate::ftw_tag::FtwTag;
-    use crate::ftw_target::FtwTarget;
-    use crate::ftw_template::FtwTemplate;
-    use crate::util;
+    use ftw::ftw_command::FtwCommand;
+    use ftw::ftw_machine_type::FtwMachineType;
+    use ftw::ftw_node_type::FtwNodeType;
+    use ftw::ftw_tag::FtwTag;
+    use ftw::ftw_target::FtwTarget;
+    use ftw::ftw_template::FtwTemplate;
+    use ftw::util;
 
     #[test]
     fn test_parse_matches_new() {
@@ -246,10 +544,135 @@ mod main_tests {
         let cmd = FtwCommand::Class {
             class_name: class_name.to_string(),
             node_type: FtwNodeType::Area2D,
+            properties: vec![],
+            signals: vec![],
+            extends_scene: None,
+            children: vec![],
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_parse_matches_class_with_props_and_signals() {
+        let app = get_clap_command();
+        let args = [
+            crate_name!(),
+            "class",
+            "Player",
+            "KinematicBody2D",
+            "--prop",
+            "speed:f32=200",
+            "--prop",
+            "health:i64",
+            "--signal",
+            "died",
+            "--signal",
+            "hit:i64",
+        ];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::Class {
+            class_name: "Player".to_string(),
+            node_type: FtwNodeType::KinematicBody2D,
+            properties: vec!["speed:f32=200".to_string(), "health:i64".to_string()],
+            signals: vec!["died".to_string(), "hit:i64".to_string()],
+            extends_scene: None,
+            children: vec![],
         };
         assert_eq!(command, cmd);
     }
 
+    #[test]
+    fn test_parse_matches_class_extends_scene() {
+        let app = get_clap_command();
+        let args = [
+            crate_name!(),
+            "class",
+            "Goblin",
+            "--extends-scene",
+            "scenes/enemies/Enemy.tscn",
+            "--child",
+            "Sprite",
+            "--child",
+            "CollisionShape2D",
+        ];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::Class {
+            class_name: "Goblin".to_string(),
+            node_type: FtwNodeType::Node,
+            properties: vec![],
+            signals: vec![],
+            extends_scene: Some("scenes/enemies/Enemy.tscn".to_string()),
+            children: vec!["Sprite".to_string(), "CollisionShape2D".to_string()],
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_parse_matches_attach() {
+        let app = get_clap_command();
+        let args = [crate_name!(), "attach", "godot/scenes/Boss.tscn"];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::Attach {
+            scene: "godot/scenes/Boss.tscn".to_string(),
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_parse_matches_bindings() {
+        let app = get_clap_command();
+        let args = [crate_name!(), "bindings", "enemies/Boss"];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::Bindings {
+            class_name: "enemies/Boss".to_string(),
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_parse_matches_resource() {
+        let app = get_clap_command();
+        let args = [
+            crate_name!(),
+            "resource",
+            "items/ItemData",
+            "--prop",
+            "damage:i32",
+            "--prop",
+            "icon:Texture",
+        ];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::Resource {
+            class_name: "items/ItemData".to_string(),
+            properties: vec!["damage:i32".to_string(), "icon:Texture".to_string()],
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_get_project_directory() {
+        let args = [crate_name!(), "-C", "games/demo", "class", "IronMan"];
+        let matches = get_clap_command().get_matches_from(args);
+        assert_eq!(
+            Some("games/demo".to_string()),
+            get_project_directory(&matches)
+        );
+        let args = [crate_name!(), "build", "--project", "games/demo"];
+        let matches = get_clap_command().get_matches_from(args);
+        assert_eq!(
+            Some("games/demo".to_string()),
+            get_project_directory(&matches)
+        );
+        let args = [crate_name!(), "clean"];
+        let matches = get_clap_command().get_matches_from(args);
+        assert_eq!(None, get_project_directory(&matches));
+    }
+
     #[test]
     fn test_parse_matches_class_no_node_type() {
         let app = get_clap_command();
@@ -260,6 +683,10 @@ mod main_tests {
         let cmd = FtwCommand::Class {
             class_name: class_name.to_string(),
             node_type: FtwNodeType::Node,
+            properties: vec![],
+            signals: vec![],
+            extends_scene: None,
+            children: vec![],
         };
         assert_eq!(command, cmd);
     }
@@ -285,6 +712,10 @@ mod main_tests {
         let command = parse_matches(&matches);
         let cmd = FtwCommand::Run {
             machine_type: FtwMachineType::Desktop,
+            build_type: FtwBuildType::Debug,
+            exported: None,
+            options: FtwRunOptions::default(),
+            instances: vec![],
         };
         assert_eq!(command, cmd);
     }
@@ -297,10 +728,131 @@ mod main_tests {
         let command = parse_matches(&matches);
         let cmd = FtwCommand::Run {
             machine_type: FtwMachineType::Server,
+            build_type: FtwBuildType::Debug,
+            exported: None,
+            options: FtwRunOptions::default(),
+            instances: vec![],
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_parse_matches_run_release() {
+        let app = get_clap_command();
+        let args = [crate_name!(), "run", "--release"];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::Run {
+            machine_type: FtwMachineType::Desktop,
+            build_type: FtwBuildType::Release,
+            exported: None,
+            options: FtwRunOptions::default(),
+            instances: vec![],
         };
         assert_eq!(command, cmd);
     }
 
+    #[test]
+    fn test_parse_matches_run_exported() {
+        let app = get_clap_command();
+        let args = [
+            crate_name!(),
+            "run",
+            "--exported",
+            "linux-x86_64",
+            "release",
+        ];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::Run {
+            machine_type: FtwMachineType::Desktop,
+            build_type: FtwBuildType::Release,
+            exported: Some(FtwTarget::LinuxX86_64),
+            options: FtwRunOptions::default(),
+            instances: vec![],
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_parse_matches_run_exported_no_target() {
+        let app = get_clap_command();
+        let args = [crate_name!(), "run", "--release", "--exported"];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let target = util::get_current_platform().parse().unwrap_or_default();
+        let cmd = FtwCommand::Run {
+            machine_type: FtwMachineType::Desktop,
+            build_type: FtwBuildType::Release,
+            exported: Some(target),
+            options: FtwRunOptions::default(),
+            instances: vec![],
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_parse_matches_run_scene() {
+        let app = get_clap_command();
+        let args = [
+            crate_name!(),
+            "run",
+            "--scene",
+            "levels/Level2",
+            "--verbose",
+            "--debug-collisions",
+            "--debug-navigation",
+            "--",
+            "--player-id",
+            "2",
+        ];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::Run {
+            machine_type: FtwMachineType::Desktop,
+            build_type: FtwBuildType::Debug,
+            exported: None,
+            options: FtwRunOptions {
+                scene: Some("levels/Level2".to_string()),
+                verbose: true,
+                debug_collisions: true,
+                debug_navigation: true,
+                user_args: vec!["--player-id".to_string(), "2".to_string()],
+            },
+            instances: vec![],
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_parse_matches_run_server_and_clients() {
+        let app = get_clap_command();
+        let args = [
+            crate_name!(),
+            "run",
+            "--server",
+            "--clients",
+            "2",
+            "--",
+            "--player-id",
+            "{instance}",
+        ];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::Run {
+            machine_type: FtwMachineType::Desktop,
+            build_type: FtwBuildType::Debug,
+            exported: None,
+            options: FtwRunOptions {
+                user_args: vec!["--player-id".to_string(), "{instance}".to_string()],
+                ..FtwRunOptions::default()
+            },
+            instances: FtwInstance::get_instances(true, 2),
+        };
+        assert_eq!(command, cmd);
+        assert_eq!(3, FtwInstance::get_instances(true, 2).len());
+    }
+
     #[test]
     fn test_parse_matches_run_no_machine_type() {
         let app = get_clap_command();
@@ -309,6 +861,10 @@ mod main_tests {
         let command = parse_matches(&matches);
         let cmd = FtwCommand::Run {
             machine_type: FtwMachineType::Desktop,
+            build_type: FtwBuildType::Debug,
+            exported: None,
+            options: FtwRunOptions::default(),
+            instances: vec![],
         };
         assert_eq!(command, cmd);
     }
@@ -574,6 +1130,181 @@ mod main_tests {
         assert_eq!(command, cmd);
     }
 
+    #[test]
+    fn test_parse_matches_package() {
+        let app = get_clap_command();
+        let args = [
+            crate_name!(),
+            "package",
+            "windows-x86_64-gnu,linux-x86_64",
+            "release",
+        ];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::Package {
+            targets: vec![FtwTarget::LinuxX86_64, FtwTarget::WindowsX86_64Gnu],
+            build_type: FtwBuildType::Release,
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_parse_matches_presets_sync() {
+        let app = get_clap_command();
+        let args = [crate_name!(), "presets", "sync"];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        assert_eq!(command, FtwCommand::PresetsSync);
+    }
+
+    #[test]
+    fn test_parse_matches_android_keystore() {
+        let app = get_clap_command();
+        let args = [crate_name!(), "android", "keystore", "release"];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::AndroidKeystore {
+            build_type: FtwBuildType::Release,
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_parse_matches_android_keystore_no_build_type() {
+        let app = get_clap_command();
+        let args = [crate_name!(), "android", "keystore"];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::AndroidKeystore {
+            build_type: FtwBuildType::Debug,
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_parse_matches_watch() {
+        let app = get_clap_command();
+        let args = [crate_name!(), "watch", "linux-x86_64", "release", "--run"];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::Watch {
+            target: FtwTarget::LinuxX86_64,
+            build_type: FtwBuildType::Release,
+            run: true,
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_parse_matches_watch_no_target() {
+        let app = get_clap_command();
+        let args = [crate_name!(), "watch"];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::Watch {
+            target: util::get_current_platform().parse().unwrap_or_default(),
+            build_type: FtwBuildType::Debug,
+            run: false,
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_parse_matches_test() {
+        let app = get_clap_command();
+        let args = [
+            crate_name!(),
+            "test",
+            "--runner",
+            "res://tests/TestRunner.tscn",
+            "--junit",
+            "report.xml",
+        ];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::Test {
+            runner: Some("res://tests/TestRunner.tscn".to_string()),
+            junit: Some("report.xml".to_string()),
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_parse_matches_test_no_options() {
+        let app = get_clap_command();
+        let args = [crate_name!(), "test"];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::Test {
+            runner: None,
+            junit: None,
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_parse_matches_check() {
+        let app = get_clap_command();
+        let args = [crate_name!(), "check", "--fix"];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        assert_eq!(command, FtwCommand::Check { fix: true });
+    }
+
+    #[test]
+    fn test_parse_matches_check_no_fix() {
+        let app = get_clap_command();
+        let args = [crate_name!(), "check"];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        assert_eq!(command, FtwCommand::Check { fix: false });
+    }
+
+    #[test]
+    fn test_parse_matches_upgrade() {
+        let app = get_clap_command();
+        let args = [
+            crate_name!(),
+            "upgrade",
+            "v1.5.0",
+            "--template",
+            "/path/to/custom/template",
+        ];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::Upgrade {
+            template: FtwTemplate::Custom {
+                git_url: "/path/to/custom/template".to_string(),
+            },
+            tag: FtwTag::Tagged {
+                git_tag: "v1.5.0".to_string(),
+            },
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_parse_matches_upgrade_no_tag() {
+        let app = get_clap_command();
+        let args = [crate_name!(), "upgrade"];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        let cmd = FtwCommand::Upgrade {
+            template: FtwTemplate::default(),
+            tag: FtwTag::Latest,
+        };
+        assert_eq!(command, cmd);
+    }
+
+    #[test]
+    fn test_parse_matches_artifacts() {
+        let app = get_clap_command();
+        let args = [crate_name!(), "artifacts"];
+        let matches = app.get_matches_from(args);
+        let command = parse_matches(&matches);
+        assert_eq!(command, FtwCommand::Artifacts);
+    }
+
     #[test]
     fn test_clean() {
         let app = get_clap_command();
@@ -32,7 +32,7 @@ mod run_command_tests {
         let result = cmd!(gogogo).run();
         assert!(result.is_err());
         match result.unwrap_err() {
-            FtwError::Error(_) => assert!(true),
+            FtwError::Error(_) => {}
             _ => unreachable!(),
         }
     }
@@ -0,0 +1,16 @@
+// ftw bindings begin
+// Generated from `{{scene_path}}`, run `ftw bindings {{class_path}}` after
+// changing the scene and a renamed or moved node will fail to compile.
+pub mod bindings {
+    use gdnative::api;
+    use gdnative::prelude::{NodeExt, TRef};
+{%- for binding in bindings %}
+
+    /// The `{{binding.path}}` node
+    pub fn {{binding.function_name}}(owner: &api::{{node_type}}) -> TRef<'_, api::{{binding.node_type}}> {
+        unsafe { owner.get_node_as::<api::{{binding.node_type}}>("{{binding.path}}") }
+            .expect("{{binding.path}} should be a {{binding.node_type}}")
+    }
+{%- endfor %}
+}
+// ftw bindings end
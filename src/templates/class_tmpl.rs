@@ -6,6 +6,10 @@ use gdnative::prelude::*;
 #[inherit({{node_type}})]
 #[register_with(Self::register_builder)]
 pub struct {{class_name}} {
+{%- for property in properties %}
+    #[property{% if property.has_default %}(default = {{property.rust_default}}){% endif %}]
+    {{property.name}}: {{property.rust_type}},
+{%- endfor %}
     name: String,
 }
 
@@ -14,7 +18,15 @@ pub struct {{class_name}} {
 #[methods]
 impl {{class_name}} {
     // Register the builder for methods, properties and/or signals.
-    fn register_builder(_builder: &ClassBuilder<Self>) {
+    fn register_builder({% if signals.size == 0 %}_{% endif %}builder: &ClassBuilder<Self>) {
+{%- for signal in signals %}
+        builder
+            .signal("{{signal.name}}")
+{%- for param in signal.params %}
+            .with_param("{{param.name}}", VariantType::{{param.variant_type}})
+{%- endfor %}
+            .done();
+{%- endfor %}
         godot_print!("{{class_name}} builder is registered!");
     }
 
@@ -22,6 +34,9 @@ impl {{class_name}} {
     fn new(_owner: &{{node_type}}) -> Self {
         godot_print!("{{class_name}} is created!");
         {{class_name}} {
+{%- for property in properties %}
+            {{property.name}}: {{property.rust_default}},
+{%- endfor %}
             name: "".to_string(),
         }
     }
@@ -0,0 +1,32 @@
+use gdnative::api::*;
+use gdnative::prelude::*;
+
+/// The {{class_name}} "resource"
+#[derive(NativeClass)]
+#[inherit(Resource)]
+#[register_with(Self::register_builder)]
+pub struct {{class_name}} {
+{%- for property in properties %}
+    #[property{% if property.has_default %}(default = {{property.rust_default}}){% endif %}]
+    {{property.name}}: {{property.rust_type}},
+{%- endfor %}
+}
+
+// The exported properties are edited in the inspector and saved inside the
+// `.tres` files that use this resource as their script.
+#[methods]
+impl {{class_name}} {
+    // Register the builder for methods, properties and/or signals.
+    fn register_builder(_builder: &ClassBuilder<Self>) {
+        godot_print!("{{class_name}} builder is registered!");
+    }
+
+    /// The "constructor" of the resource.
+    fn new(_owner: &Resource) -> Self {
+        {{class_name}} {
+{%- for property in properties %}
+            {{property.name}}: {{property.rust_default}},
+{%- endfor %}
+        }
+    }
+}
@@ -1,9 +1,12 @@
 #![allow(dead_code)]
+use crate::ftw_error::FtwError;
+use crate::ftw_target::FtwTarget;
 use nanoid::nanoid;
-use std::fs::{remove_dir, remove_dir_all, File};
+use std::fs::{create_dir_all, remove_dir, remove_dir_all, write, File};
 use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str;
+use strum::IntoEnumIterator;
 
 #[derive(Debug)]
 pub struct Project {
@@ -74,3 +77,36 @@ impl Drop for Project {
         drop(remove_dir(&self.root));
     }
 }
+
+/// Creates the files that make `root` a valid project without generating it
+/// from a template
+///
+/// # Errors
+///
+/// Will return `Err` if the files cannot be created
+#[cfg(not(tarpaulin_include))]
+pub fn create_project_skeleton(root: &Path) -> Result<(), FtwError> {
+    let files = [
+        ("Cargo.toml", "[workspace]\nmembers = [\"rust\"]\n"),
+        ("Makefile.toml", ""),
+        ("godot/default_env.tres", ""),
+        ("godot/export_presets.cfg", ""),
+        ("godot/native/game.gdnlib", ""),
+        ("godot/project.godot", ""),
+        ("rust/src/lib.rs", ""),
+        ("rust/Cargo.toml", "[package]\nname = \"game\"\n"),
+    ];
+    for (file, contents) in files {
+        let path = root.join(file);
+        create_dir_all(path.parent().ok_or(FtwError::PathError)?)?;
+        write(path, contents)?;
+    }
+    for target in FtwTarget::iter() {
+        for dir in ["bin", "lib"] {
+            let path = root.join(dir).join(target.to_string());
+            create_dir_all(&path)?;
+            write(path.join(".gitkeep"), "")?;
+        }
+    }
+    Ok(())
+}
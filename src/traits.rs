@@ -1,14 +1,14 @@
 use crate::ftw_error::FtwError;
 use crate::type_alias::{
-    AppExt, CliArg, ExportArg, ExportName, FtwResult, GitTag, GitUrl, LibExt, LibPrefix, Message,
-    StrTarget,
+    AppExt, CliArg, ExportArg, ExportName, FtwResult, GdnlibFeatures, GitTag, GitUrl, LibExt,
+    LibPrefix, Message, StrTarget,
 };
 
 pub trait Processor {
     /// # Errors
     ///
     /// Will return `Err` if an error happened in the implementation
-    fn process(&self) -> FtwResult;
+    fn process(&self) -> FtwResult<'_>;
 }
 
 pub trait Runner {
@@ -23,10 +23,12 @@ pub trait Compiler {
     ///
     /// Will return `Err` if an error happened in the implementation
     fn clean(&self) -> Result<(), FtwError>;
+    /// Returns `false` if the installed library was already up to date
+    ///
     /// # Errors
     ///
     /// Will return `Err` if an error happened in the implementation
-    fn build(&self) -> Result<(), FtwError>;
+    fn build(&self) -> Result<bool, FtwError>;
     /// # Errors
     ///
     /// Will return `Err` if an error happened in the implementation
@@ -72,3 +74,7 @@ pub trait ToMessage {
 pub trait ToStrTarget {
     fn to_str_target(&self) -> StrTarget;
 }
+
+pub trait ToGdnlibFeatures {
+    fn to_gdnlib_features(&self) -> GdnlibFeatures;
+}
@@ -13,4 +13,5 @@ pub type AppExt = String;
 pub type LibPrefix = String;
 pub type Message = String;
 pub type StrTarget = String;
+pub type GdnlibFeatures = String;
 pub type FtwResult<'a> = Result<FtwSuccess<'a>, FtwError>;
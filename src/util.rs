@@ -3,10 +3,35 @@ use crate::ftw_error::FtwError;
 use crate::ftw_machine_type::FtwMachineType;
 use crate::ftw_target::FtwTarget;
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use std::env;
 use std::fs::File;
-use std::io::Read;
-use std::path::Path;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::process::{self, Command};
+use std::sync::{Mutex, Once};
+use voca_rs::Voca;
+
+static CTRL_C_HOOKS: Mutex<Vec<fn()>> = Mutex::new(Vec::new());
+static CTRL_C_HANDLER: Once = Once::new();
+
+/// Calls `hook` when Ctrl-C is pressed, every hook is called in the order it
+/// was added before exiting
+pub fn on_ctrl_c(hook: fn()) {
+    CTRL_C_HANDLER.call_once(|| {
+        drop(ctrlc::set_handler(|| {
+            if let Ok(hooks) = CTRL_C_HOOKS.lock() {
+                for hook in hooks.iter() {
+                    hook();
+                }
+            }
+            process::exit(130);
+        }));
+    });
+    if let Ok(mut hooks) = CTRL_C_HOOKS.lock() {
+        hooks.push(hook);
+    }
+}
 
 #[must_use]
 pub fn get_current_platform() -> String {
@@ -30,21 +55,163 @@ pub fn get_class_name_and_directories(class_name: &str) -> (String, Vec<String>)
     }
 }
 
+/// Maps a scene to its path inside `godot/`, a `.tscn` file can be given
+/// with or without `godot/` or `res://`, a class name is mapped to the scene
+/// that `ftw class` creates for it (e.g. `enemies/Goblin` to
+/// `scenes/enemies/Goblin.tscn`)
+#[must_use]
+pub fn get_scene_path(scene: &str) -> String {
+    if Path::new(scene)
+        .extension()
+        .is_some_and(|ext| ext == "tscn")
+    {
+        let scene = scene.replace('\\', "/");
+        let scene = scene.strip_prefix("res://").unwrap_or(&scene);
+        let scene = scene.strip_prefix("godot/").unwrap_or(scene);
+        scene.to_string()
+    } else {
+        let (class_name, directories) = get_class_name_and_directories(scene);
+        let class_name_pascal_case = class_name._pascal_case();
+        let directories: String = directories
+            .iter()
+            .map(|directory| format!("{directory}/"))
+            .collect();
+        format!("scenes/{directories}{class_name_pascal_case}.tscn")
+    }
+}
+
+/// The directories of a class implied by `path` (relative to the project
+/// root) when it is inside `rust/src`, `godot/scenes`, `godot/native` or
+/// `godot/resources`
+#[must_use]
+pub fn get_class_directories_from_path(path: &Path) -> Vec<String> {
+    [
+        "rust/src",
+        "godot/scenes",
+        "godot/native",
+        "godot/resources",
+    ]
+    .iter()
+    .find_map(|base_path| path.strip_prefix(base_path).ok())
+    .map(|directories| {
+        directories
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(directory) => directory.to_str().map(String::from),
+                _ => None,
+            })
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+/// Resolves `path` against `current_dir` and returns it relative to `root`
+/// when it is inside of it, absolute otherwise (e.g. `../scenes/Hero.tscn`
+/// from `godot/native` becomes `godot/scenes/Hero.tscn`)
+#[must_use]
+pub fn get_path_relative_to(current_dir: &Path, root: &Path, path: &str) -> String {
+    let path = current_dir
+        .join(path)
+        .components()
+        .fold(PathBuf::new(), |mut path, component| {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    path.pop();
+                }
+                component => path.push(component),
+            }
+            path
+        });
+    path.strip_prefix(root)
+        .unwrap_or(&path)
+        .display()
+        .to_string()
+}
+
 #[must_use]
-pub fn get_godot_exe_for_exporting(current_platform: FtwTarget) -> String {
-    let ftw_cfg = FtwConfiguration::new();
+pub fn get_godot_exe_for_exporting(
+    ftw_cfg: &FtwConfiguration,
+    current_platform: FtwTarget,
+) -> String {
     match current_platform {
-        FtwTarget::LinuxX86 | FtwTarget::LinuxX86_64 => ftw_cfg.godot_headless_executable,
-        _ => ftw_cfg.godot_executable,
+        FtwTarget::LinuxX86 | FtwTarget::LinuxX86_64 => ftw_cfg.godot_headless_executable.clone(),
+        _ => ftw_cfg.godot_executable.clone(),
     }
 }
 
 #[must_use]
-pub fn get_godot_exe_for_running(machine_type: &FtwMachineType) -> String {
-    let ftw_cfg = FtwConfiguration::new();
+pub fn get_godot_exe_for_running(
+    ftw_cfg: &FtwConfiguration,
+    machine_type: &FtwMachineType,
+) -> String {
     match machine_type {
-        FtwMachineType::Desktop => ftw_cfg.godot_executable,
-        FtwMachineType::Server => ftw_cfg.godot_server_executable,
+        FtwMachineType::Desktop => ftw_cfg.godot_executable.clone(),
+        FtwMachineType::Server => ftw_cfg.godot_server_executable.clone(),
+    }
+}
+
+/// Returns the last line printed by `godot_executable --version`, `None` if
+/// it cannot be run
+#[must_use]
+pub fn get_godot_version(godot_executable: &str) -> Option<String> {
+    let output = Command::new(godot_executable)
+        .arg("--version")
+        .output()
+        .ok()?;
+    let output = String::from_utf8(output.stdout).ok()?;
+    let version = output.lines().last()?.trim().to_string();
+    (!version.is_empty()).then_some(version)
+}
+
+fn get_godot_major_version(godot_executable: &str) -> Option<u32> {
+    get_godot_version(godot_executable)?
+        .split('.')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// The executable (and its arguments) that runs the godot tests without a
+/// window, the one used for exporting plus `--headless` for Godot 4 or newer
+#[must_use]
+pub fn get_godot_exe_for_testing(ftw_cfg: &FtwConfiguration) -> (String, Vec<String>) {
+    let current_platform = get_current_platform().parse().unwrap_or_default();
+    let godot_executable = get_godot_exe_for_exporting(ftw_cfg, current_platform);
+    let godot_args = match get_godot_major_version(&godot_executable) {
+        Some(major_version) if major_version >= 4 => vec!["--headless".to_string()],
+        _ => vec![],
+    };
+    (godot_executable, godot_args)
+}
+
+/// Finds the executable (and its arguments) that runs the game as a server,
+/// the configured `godot-server-exe` if it can be run or else the configured
+/// `godot-exe` with `--headless` if it is Godot 4 or newer
+///
+/// # Errors
+///
+/// Will return `Err` if none of the executables can run the game as a server
+pub fn get_godot_exe_for_server(
+    ftw_cfg: &FtwConfiguration,
+) -> Result<(String, Vec<String>), FtwError> {
+    find_godot_exe_for_server(&ftw_cfg.godot_server_executable, &ftw_cfg.godot_executable)
+}
+
+fn find_godot_exe_for_server(
+    godot_server_executable: &str,
+    godot_executable: &str,
+) -> Result<(String, Vec<String>), FtwError> {
+    if get_godot_version(godot_server_executable).is_some() {
+        return Ok((godot_server_executable.to_string(), vec![]));
+    }
+    match get_godot_major_version(godot_executable) {
+        Some(major_version) if major_version >= 4 => {
+            Ok((godot_executable.to_string(), vec!["--headless".to_string()]))
+        }
+        _ => Err(FtwError::MissingServerExecutable(
+            godot_server_executable.to_string(),
+        )),
     }
 }
 
@@ -56,33 +223,81 @@ struct CargoToml {
 #[derive(Debug, Deserialize)]
 struct Package {
     name: Option<String>,
+    version: Option<String>,
 }
 
-/// # Errors
-///
-/// Will return `Err` can be any of the following:  Cargo.toml cannot be found on the given path, Cargo.toml cannot be parsed or Cargo.toml doesn't have a package.name entry
-pub fn get_crate_name_from_path(path: &str) -> Result<String, FtwError> {
-    let cargo_file = Path::new(path).join("Cargo.toml");
+fn get_cargo_toml_from_path(path: &Path) -> Result<CargoToml, FtwError> {
+    let cargo_file = path.join("Cargo.toml");
     let mut file_contents = String::new();
     let mut file = File::open(cargo_file.as_path())?;
     file.read_to_string(&mut file_contents)?;
     let cargo_toml: CargoToml = toml::from_str(file_contents.as_str())?;
-    let crate_name = cargo_toml
+    Ok(cargo_toml)
+}
+
+/// # Errors
+///
+/// Will return `Err` can be any of the following:  Cargo.toml cannot be found on the given path, Cargo.toml cannot be parsed or Cargo.toml doesn't have a package.name entry
+pub fn get_crate_name_from_path(path: &Path) -> Result<String, FtwError> {
+    let crate_name = get_cargo_toml_from_path(path)?
         .package
         .name
         .ok_or(FtwError::MissingPackageNameError)?;
     Ok(crate_name)
 }
 
+/// # Errors
+///
+/// Will return `Err` can be any of the following:  Cargo.toml cannot be found on the given path, Cargo.toml cannot be parsed or Cargo.toml doesn't have a package.version entry
+pub fn get_crate_version_from_path(path: &Path) -> Result<String, FtwError> {
+    let crate_version = get_cargo_toml_from_path(path)?
+        .package
+        .version
+        .ok_or(FtwError::MissingPackageVersionError)?;
+    Ok(crate_version)
+}
+
+/// # Errors
+///
+/// Will return `Err` if the file cannot be opened or read
+pub fn get_sha256_from_path(path: &Path) -> Result<String, FtwError> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    let hash = hasher.finalize();
+    Ok(hash.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+#[must_use]
+pub fn get_git_commit(root: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(root)
+        .output()
+        .ok()?;
+    let commit = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (output.status.success() && !commit.is_empty()).then_some(commit)
+}
+
+#[must_use]
+pub fn is_git_dirty(root: &Path) -> bool {
+    Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(root)
+        .output()
+        .is_ok_and(|output| output.status.success() && !output.stdout.is_empty())
+}
+
 #[cfg(test)]
 mod util_tests {
     use super::*;
 
     #[test]
     fn test_get_godot_exe_for_exporting() {
+        let ftw_cfg = FtwConfiguration::default();
         let linux_desktop_platforms = [FtwTarget::LinuxX86, FtwTarget::LinuxX86_64];
         for p in linux_desktop_platforms {
-            let godot_exe = get_godot_exe_for_exporting(p);
+            let godot_exe = get_godot_exe_for_exporting(&ftw_cfg, p);
             assert_eq!("godot3-headless".to_string(), godot_exe);
         }
         let other_desktop_platforms = [
@@ -93,21 +308,85 @@ mod util_tests {
             FtwTarget::WindowsX86_64Msvc,
         ];
         for p in other_desktop_platforms {
-            let godot_exe = get_godot_exe_for_exporting(p);
+            let godot_exe = get_godot_exe_for_exporting(&ftw_cfg, p);
             assert_eq!("godot3".to_string(), godot_exe);
         }
     }
 
     #[test]
     fn test_get_godot_exe_for_running() {
+        let ftw_cfg = FtwConfiguration::default();
         let machine_type = FtwMachineType::Desktop;
-        let godot_exe = get_godot_exe_for_running(&machine_type);
+        let godot_exe = get_godot_exe_for_running(&ftw_cfg, &machine_type);
         assert_eq!("godot3".to_string(), godot_exe);
         let machine_type = FtwMachineType::Server;
-        let godot_exe = get_godot_exe_for_running(&machine_type);
+        let godot_exe = get_godot_exe_for_running(&ftw_cfg, &machine_type);
         assert_eq!("godot3-server".to_string(), godot_exe);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_find_godot_exe_for_server() -> Result<(), FtwError> {
+        use std::fs;
+        use std::os::unix::fs::PermissionsExt;
+        let dir = env::temp_dir().join(format!("ftw-util-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let godot_versions = [
+            ("godot3", "3.5.3.stable.official"),
+            ("godot4", "4.2.1.stable.official"),
+        ];
+        for (godot_executable, godot_version) in godot_versions {
+            let path = dir.join(godot_executable);
+            fs::write(&path, format!("#!/bin/sh\necho {godot_version}\n"))?;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+        }
+        let godot3 = dir.join("godot3").display().to_string();
+        let godot4 = dir.join("godot4").display().to_string();
+        assert_eq!(
+            Some("4.2.1.stable.official".to_string()),
+            get_godot_version(&godot4)
+        );
+        assert_eq!(None, get_godot_version("gogogo"));
+        assert_eq!(
+            (godot3.clone(), vec![]),
+            find_godot_exe_for_server(&godot3, &godot4)?
+        );
+        assert_eq!(
+            (godot4.clone(), vec!["--headless".to_string()]),
+            find_godot_exe_for_server("gogogo", &godot4)?
+        );
+        assert!(matches!(
+            find_godot_exe_for_server("gogogo", &godot3),
+            Err(FtwError::MissingServerExecutable(godot_server_executable)) if godot_server_executable == "gogogo"
+        ));
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_sha256_from_path() -> Result<(), FtwError> {
+        let hash = get_sha256_from_path(Path::new("LICENSE"))?;
+        assert_eq!(64, hash.len());
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+        assert!(get_sha256_from_path(Path::new("no/such/file")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_crate_version_from_path() -> Result<(), FtwError> {
+        let crate_version = get_crate_version_from_path(Path::new("."))?;
+        assert_eq!(env!("CARGO_PKG_VERSION"), crate_version);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_git_commit() {
+        let commit = get_git_commit(Path::new("."));
+        if let Some(commit) = commit {
+            assert_eq!(40, commit.len());
+        }
+    }
+
     #[test]
     fn test_get_class_name_and_directories() {
         let class_name = "IronMan";
@@ -115,6 +394,76 @@ mod util_tests {
         assert_eq!(("IronMan".to_string(), vec![]), v);
     }
 
+    #[test]
+    fn test_get_scene_path() {
+        for scene in [
+            "godot/scenes/enemies/Boss.tscn",
+            "res://scenes/enemies/Boss.tscn",
+            "scenes/enemies/Boss.tscn",
+            "enemies/Boss",
+            "enemies/boss",
+        ] {
+            assert_eq!("scenes/enemies/Boss.tscn", get_scene_path(scene));
+        }
+    }
+
+    #[test]
+    fn test_get_class_directories_from_path() {
+        let enemies = vec!["enemies".to_string()];
+        assert_eq!(
+            enemies,
+            get_class_directories_from_path(Path::new("rust/src/enemies"))
+        );
+        assert_eq!(
+            vec!["enemies".to_string(), "bosses".to_string()],
+            get_class_directories_from_path(Path::new("godot/scenes/enemies/bosses"))
+        );
+        assert_eq!(
+            enemies,
+            get_class_directories_from_path(Path::new("godot/native/enemies"))
+        );
+        assert_eq!(
+            vec!["items".to_string()],
+            get_class_directories_from_path(Path::new("godot/resources/items"))
+        );
+        let no_directories: Vec<String> = vec![];
+        assert_eq!(
+            no_directories,
+            get_class_directories_from_path(Path::new("rust/src"))
+        );
+        assert_eq!(
+            no_directories,
+            get_class_directories_from_path(Path::new("godot/assets/enemies"))
+        );
+        assert_eq!(
+            no_directories,
+            get_class_directories_from_path(Path::new(""))
+        );
+    }
+
+    #[test]
+    fn test_get_path_relative_to() {
+        let root = Path::new("/home/user/game");
+        let native = root.join("godot/native");
+        assert_eq!(
+            "godot/scenes/Hero.tscn",
+            get_path_relative_to(&native, root, "../scenes/Hero.tscn")
+        );
+        assert_eq!(
+            "godot/native/Hero.gdns",
+            get_path_relative_to(&native, root, "./Hero.gdns")
+        );
+        assert_eq!("report.xml", get_path_relative_to(root, root, "report.xml"));
+        assert_eq!(
+            "/home/user/report.xml",
+            get_path_relative_to(&native, root, "../../../report.xml")
+        );
+        assert_eq!(
+            "/tmp/report.xml",
+            get_path_relative_to(&native, root, "/tmp/report.xml")
+        );
+    }
+
     #[test]
     fn test_get_class_name_and_directories_with_slashes() {
         let class_name = "marvel/avengers/IronMan";
@@ -0,0 +1,60 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use common::ftw;
+use ftw::test_util::Project;
+use predicates::prelude::*;
+use std::fs::{create_dir_all, write};
+use std::path::Path;
+
+const BOSS_TSCN: &str = r#"[gd_scene format=2]
+
+[node name="Boss" type="KinematicBody2D"]
+
+[node name="Sprite" type="Sprite" parent="."]
+"#;
+
+#[test]
+fn test_ftw_attach() {
+    let project = Project::new();
+    ftw()
+        .arg("new")
+        .arg(project.get_name())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("SUCCESS").from_utf8());
+    let scenes_dir = Path::new(&project.get_name()).join("godot/scenes/enemies");
+    create_dir_all(&scenes_dir).unwrap();
+    write(scenes_dir.join("Boss.tscn"), BOSS_TSCN).unwrap();
+    ftw()
+        .arg("attach")
+        .arg("godot/scenes/enemies/Boss.tscn")
+        .current_dir(project.get_name())
+        .assert()
+        .success();
+    assert!(project.exists("rust/src/enemies/boss.rs"));
+    assert!(project.exists("godot/native/enemies/Boss.gdns"));
+    assert!(project
+        .read("rust/src/enemies/boss.rs")
+        .contains("#[inherit(KinematicBody2D)]"));
+    assert!(project
+        .read("godot/scenes/enemies/Boss.tscn")
+        .contains("[ext_resource path=\"res://native/enemies/Boss.gdns\" type=\"Script\" id=1]"));
+    assert!(project
+        .read("godot/scenes/enemies/Boss.tscn")
+        .contains("[node name=\"Boss\" type=\"KinematicBody2D\"]\nscript = ExtResource( 1 )\n"));
+    assert!(project
+        .read("godot/scenes/enemies/Boss.tscn")
+        .contains("[node name=\"Sprite\" type=\"Sprite\" parent=\".\"]"));
+    assert!(project
+        .read("rust/src/lib.rs")
+        .contains("handle.add_class::<enemies::boss::Boss>();"));
+    //
+    ftw()
+        .arg("attach")
+        .arg("godot/scenes/enemies/Boss.tscn")
+        .current_dir(project.get_name())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("already has a script").from_utf8());
+}
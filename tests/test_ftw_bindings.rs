@@ -0,0 +1,60 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use common::ftw;
+use ftw::test_util::Project;
+use predicates::prelude::*;
+use std::fs::write;
+use std::path::Path;
+
+const BOSS_TSCN: &str = r#"[gd_scene format=2]
+
+[node name="Boss" type="KinematicBody2D"]
+
+[node name="Sprite" type="Sprite" parent="."]
+
+[node name="Anim" type="AnimationPlayer" parent="Sprite"]
+"#;
+
+#[test]
+fn test_ftw_bindings() {
+    let project = Project::new();
+    ftw()
+        .arg("new")
+        .arg(project.get_name())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("SUCCESS").from_utf8());
+    ftw()
+        .arg("class")
+        .arg("enemies/Boss")
+        .arg("KinematicBody2D")
+        .current_dir(project.get_name())
+        .assert()
+        .success();
+    let scene_file = Path::new(&project.get_name()).join("godot/scenes/enemies/Boss.tscn");
+    write(scene_file, BOSS_TSCN).unwrap();
+    for _ in 0..2 {
+        ftw()
+            .arg("bindings")
+            .arg("enemies/Boss")
+            .current_dir(project.get_name())
+            .assert()
+            .success()
+            .stdout(predicates::str::contains("2 node(s)").from_utf8());
+    }
+    let class_rs = project.read("rust/src/enemies/boss.rs");
+    assert_eq!(1, class_rs.matches("// ftw bindings begin").count());
+    assert!(class_rs.contains(
+        "pub fn sprite_anim(owner: &api::KinematicBody2D) -> TRef<'_, api::AnimationPlayer> {"
+    ));
+    assert!(class_rs.contains("owner.get_node_as::<api::Sprite>(\"Sprite\")"));
+    //
+    ftw()
+        .arg("bindings")
+        .arg("Goblin")
+        .current_dir(project.get_name())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("could not be found").from_utf8());
+}
@@ -7,7 +7,6 @@ use ftw::test_util::Project;
 use ftw::traits::{ToCliArg, ToLibExt, ToLibPrefix, ToStrTarget};
 use ftw::type_alias::StrTarget;
 use ftw::util::get_current_platform;
-use predicates;
 use predicates::prelude::*;
 
 #[test]
@@ -15,13 +14,13 @@ fn test_ftw_build_no_target() {
     let project = Project::new();
     ftw()
         .arg("new")
-        .arg(&project.get_name())
+        .arg(project.get_name())
         .assert()
         .success()
         .stdout(predicates::str::contains("SUCCESS").from_utf8());
     ftw()
         .arg("build")
-        .current_dir(&project.get_name())
+        .current_dir(project.get_name())
         .assert()
         .success();
     let current_platform = get_current_platform();
@@ -43,14 +42,14 @@ fn test_ftw_cross_build_multi_target() {
     let project = Project::new();
     ftw()
         .arg("new")
-        .arg(&project.get_name())
+        .arg(project.get_name())
         .assert()
         .success()
         .stdout(predicates::str::contains("SUCCESS").from_utf8());
     let contents = r#"[ftw]
 enable-cross-compilation=true
 "#;
-    let _ = project.create(".ftw", contents);
+    project.create(".ftw", contents);
     assert!(project
         .read(".ftw")
         .contains("enable-cross-compilation=true"));
@@ -70,7 +69,7 @@ enable-cross-compilation=true
     ftw()
         .arg("build")
         .arg(str_targets)
-        .current_dir(&project.get_name())
+        .current_dir(project.get_name())
         .assert()
         .success();
     assert!(project
@@ -87,7 +86,7 @@ enable-cross-compilation=true
     }
     ftw()
         .arg("clean")
-        .current_dir(&project.get_name())
+        .current_dir(project.get_name())
         .assert()
         .success();
 }
@@ -3,7 +3,6 @@ mod common;
 use assert_cmd::prelude::*;
 use common::ftw;
 use ftw::test_util::Project;
-use predicates;
 use predicates::prelude::*;
 
 #[test]
@@ -11,7 +10,7 @@ fn test_ftw_class() {
     let project = Project::new();
     ftw()
         .arg("new")
-        .arg(&project.get_name())
+        .arg(project.get_name())
         .assert()
         .success()
         .stdout(predicates::str::contains("SUCCESS").from_utf8());
@@ -19,7 +18,7 @@ fn test_ftw_class() {
         .arg("class")
         .arg("MyPlayer")
         .arg("Area2D")
-        .current_dir(&project.get_name())
+        .current_dir(project.get_name())
         .assert()
         .success();
     assert!(project.exists("rust/src/my_player.rs"));
@@ -50,12 +49,109 @@ fn test_ftw_class() {
         .contains("handle.add_class::<my_player::MyPlayer>();"));
 }
 
+#[test]
+fn test_ftw_class_with_props_and_signals() {
+    let project = Project::new();
+    ftw()
+        .arg("new")
+        .arg(project.get_name())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("SUCCESS").from_utf8());
+    ftw()
+        .arg("class")
+        .arg("Player")
+        .arg("KinematicBody2D")
+        .arg("--prop")
+        .arg("speed:f32=200")
+        .arg("--prop")
+        .arg("health:i64")
+        .arg("--signal")
+        .arg("died")
+        .arg("--signal")
+        .arg("hit:i64")
+        .current_dir(project.get_name())
+        .assert()
+        .success();
+    assert!(project
+        .read("rust/src/player.rs")
+        .contains("#[property(default = 200.0)]\n    speed: f32,"));
+    assert!(project
+        .read("rust/src/player.rs")
+        .contains("#[property]\n    health: i64,"));
+    assert!(project
+        .read("rust/src/player.rs")
+        .contains(".signal(\"died\")\n            .done();"));
+    assert!(project
+        .read("rust/src/player.rs")
+        .contains(".with_param(\"arg0\", VariantType::I64)"));
+    assert!(project
+        .read("godot/scenes/Player.tscn")
+        .contains("script = ExtResource( 1 )\nspeed = 200.0\n"));
+    assert!(!project.read("godot/scenes/Player.tscn").contains("health"));
+}
+
+#[test]
+fn test_ftw_class_extends_scene() {
+    let project = Project::new();
+    ftw()
+        .arg("new")
+        .arg(project.get_name())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("SUCCESS").from_utf8());
+    ftw()
+        .arg("class")
+        .arg("enemies/Enemy")
+        .arg("KinematicBody2D")
+        .current_dir(project.get_name())
+        .assert()
+        .success();
+    ftw()
+        .arg("class")
+        .arg("enemies/Goblin")
+        .arg("--extends-scene")
+        .arg("scenes/enemies/Enemy.tscn")
+        .arg("--child")
+        .arg("Sprite")
+        .arg("--child")
+        .arg("CollisionShape2D")
+        .current_dir(project.get_name())
+        .assert()
+        .success();
+    assert!(project
+        .read("rust/src/enemies/goblin.rs")
+        .contains("#[inherit(KinematicBody2D)]"));
+    assert!(project
+        .read("godot/scenes/enemies/Goblin.tscn")
+        .contains("[gd_scene load_steps=3 format=2]"));
+    assert!(project.read("godot/scenes/enemies/Goblin.tscn").contains(
+        "[ext_resource path=\"res://scenes/enemies/Enemy.tscn\" type=\"PackedScene\" id=1]"
+    ));
+    assert!(project
+        .read("godot/scenes/enemies/Goblin.tscn")
+        .contains("[node name=\"Goblin\" instance=ExtResource( 1 )]\nscript = ExtResource( 2 )\n"));
+    assert!(project
+        .read("godot/scenes/enemies/Goblin.tscn")
+        .contains("[node name=\"CollisionShape2D\" type=\"CollisionShape2D\" parent=\".\"]"));
+    //
+    ftw()
+        .arg("class")
+        .arg("Orc")
+        .arg("--child")
+        .arg("Sprit")
+        .current_dir(project.get_name())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("not a godot node type").from_utf8());
+}
+
 #[test]
 fn test_ftw_tool_class() {
     let project = Project::new();
     ftw()
         .arg("new")
-        .arg(&project.get_name())
+        .arg(project.get_name())
         .assert()
         .success()
         .stdout(predicates::str::contains("SUCCESS").from_utf8());
@@ -63,7 +159,7 @@ fn test_ftw_tool_class() {
         .arg("class")
         .arg("MyButtonTool")
         .arg("Button")
-        .current_dir(&project.get_name())
+        .current_dir(project.get_name())
         .assert()
         .success();
     assert!(project.exists("rust/src/my_button_tool.rs"));
@@ -101,14 +197,14 @@ fn test_ftw_class_no_node_type() {
     let project = Project::new();
     ftw()
         .arg("new")
-        .arg(&project.get_name())
+        .arg(project.get_name())
         .assert()
         .success()
         .stdout(predicates::str::contains("SUCCESS").from_utf8());
     ftw()
         .arg("class")
         .arg("MyPlayer")
-        .current_dir(&project.get_name())
+        .current_dir(project.get_name())
         .assert()
         .success();
     assert!(project.exists("rust/src/my_player.rs"));
@@ -144,13 +240,13 @@ fn test_ftw_class_no_class_name() {
     let project = Project::new();
     ftw()
         .arg("new")
-        .arg(&project.get_name())
+        .arg(project.get_name())
         .assert()
         .success()
         .stdout(predicates::str::contains("SUCCESS").from_utf8());
     ftw()
         .arg("class")
-        .current_dir(&project.get_name())
+        .current_dir(project.get_name())
         .assert()
         .failure()
         .stderr(predicates::str::contains("error").from_utf8());
@@ -162,7 +258,7 @@ fn test_ftw_class_with_subs() {
     let project = Project::new();
     ftw()
         .arg("new")
-        .arg(&project.get_name())
+        .arg(project.get_name())
         .assert()
         .success()
         .stdout(predicates::str::contains("SUCCESS").from_utf8());
@@ -170,7 +266,7 @@ fn test_ftw_class_with_subs() {
         .arg("class")
         .arg("foo/bar/baz/MyPlayer")
         .arg("Area2D")
-        .current_dir(&project.get_name())
+        .current_dir(project.get_name())
         .assert()
         .success();
     assert!(project.exists("rust/src/foo/bar/baz/my_player.rs"));
@@ -216,7 +312,7 @@ fn test_ftw_class_with_subs() {
         .arg("class")
         .arg("foo/bar/FooBar")
         .arg("Area2D")
-        .current_dir(&project.get_name())
+        .current_dir(project.get_name())
         .assert()
         .success();
     assert!(project.exists("rust/src/foo/bar/foo_bar.rs"));
@@ -259,7 +355,7 @@ fn test_ftw_class_with_subs() {
         .arg("class")
         .arg("foo/bar/baz/woot/Blah")
         .arg("Area2D")
-        .current_dir(&project.get_name())
+        .current_dir(project.get_name())
         .assert()
         .success();
     assert!(project.exists("rust/src/foo/bar/baz/woot/blah.rs"));
@@ -7,7 +7,6 @@ use ftw::test_util::Project;
 use ftw::traits::{ToAppExt, ToCliArg, ToLibExt, ToLibPrefix, ToStrTarget};
 use ftw::type_alias::StrTarget;
 use ftw::util::get_current_platform;
-use predicates;
 use predicates::prelude::*;
 use std::process::Command;
 
@@ -70,7 +69,7 @@ fn test_ftw_cross_export_multi_target() {
     let contents = r#"[ftw]
 enable-cross-compilation=true
 "#;
-    let _ = project.create(".ftw", contents);
+    project.create(".ftw", contents);
     assert!(project
         .read(".ftw")
         .contains("enable-cross-compilation=true"));
@@ -80,16 +79,10 @@ enable-cross-compilation=true
         .current_dir(&project_name)
         .assert()
         .success();
-    Command::new("cargo")
-        .arg("make")
-        .arg("create-debug-keystore")
-        .current_dir(&project_name)
-        .assert()
-        .success();
-    Command::new("cargo")
-        .arg("make")
-        .arg("create-release-keystore")
-        .arg(&project_name)
+    ftw()
+        .arg("android")
+        .arg("keystore")
+        .arg("debug")
         .current_dir(&project_name)
         .assert()
         .success();
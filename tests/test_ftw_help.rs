@@ -3,7 +3,6 @@ mod common;
 use assert_cmd::prelude::*;
 use clap::{crate_authors, crate_description, crate_name, crate_version};
 use common::ftw;
-use predicates;
 use predicates::prelude::*;
 
 #[test]
@@ -13,7 +12,7 @@ fn test_ftw_help() {
     let crate_name = crate_name!();
     let crate_version = crate_version!();
     let name_version = format!("{crate_name} {crate_version}");
-    let usage = format!("Usage: {crate_name} [COMMAND]");
+    let usage = format!("Usage: {crate_name} [OPTIONS] [COMMAND]");
     ftw()
         .arg("--help")
         .assert()
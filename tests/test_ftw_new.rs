@@ -3,7 +3,6 @@ mod common;
 use assert_cmd::prelude::*;
 use common::ftw;
 use ftw::test_util::Project;
-use predicates;
 use predicates::prelude::*;
 
 #[test]
@@ -11,7 +10,7 @@ fn test_ftw_new() {
     let project = Project::new();
     ftw()
         .arg("new")
-        .arg(&project.get_name())
+        .arg(project.get_name())
         .arg("default")
         .assert()
         .success()
@@ -44,7 +43,7 @@ fn test_ftw_new_with_latest_tag() {
     let project = Project::new();
     ftw()
         .arg("new")
-        .arg(&project.get_name())
+        .arg(project.get_name())
         .arg("default")
         .arg("latest")
         .assert()
@@ -78,7 +77,7 @@ fn test_ftw_new_with_v150_tag() {
     let project = Project::new();
     ftw()
         .arg("new")
-        .arg(&project.get_name())
+        .arg(project.get_name())
         .arg("default")
         .arg("v1.5.0")
         .assert()
@@ -112,7 +111,7 @@ fn test_ftw_new_with_v140_tag() {
     let project = Project::new();
     ftw()
         .arg("new")
-        .arg(&project.get_name())
+        .arg(project.get_name())
         .arg("default")
         .arg("v1.4.0")
         .assert()
@@ -146,7 +145,7 @@ fn test_ftw_new_with_v130_tag() {
     let project = Project::new();
     ftw()
         .arg("new")
-        .arg(&project.get_name())
+        .arg(project.get_name())
         .arg("default")
         .arg("v1.3.0")
         .assert()
@@ -180,7 +179,7 @@ fn test_ftw_new_with_v120_tag() {
     let project = Project::new();
     ftw()
         .arg("new")
-        .arg(&project.get_name())
+        .arg(project.get_name())
         .arg("default")
         .arg("v1.2.0")
         .assert()
@@ -214,7 +213,7 @@ fn test_ftw_new_no_template() {
     let project = Project::new();
     ftw()
         .arg("new")
-        .arg(&project.get_name())
+        .arg(project.get_name())
         .assert()
         .success()
         .stdout(predicates::str::contains("SUCCESS").from_utf8());
@@ -0,0 +1,70 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use common::ftw;
+use ftw::test_util::Project;
+use predicates::prelude::*;
+
+#[test]
+fn test_ftw_resource() {
+    let project = Project::new();
+    ftw()
+        .arg("new")
+        .arg(project.get_name())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("SUCCESS").from_utf8());
+    ftw()
+        .arg("resource")
+        .arg("ItemData")
+        .arg("--prop")
+        .arg("damage:i32")
+        .arg("--prop")
+        .arg("icon:Texture")
+        .current_dir(project.get_name())
+        .assert()
+        .success();
+    assert!(project.exists("rust/src/item_data.rs"));
+    assert!(project.exists("godot/native/ItemData.gdns"));
+    assert!(project.exists("godot/resources/ItemData.tres"));
+    assert!(project
+        .read("rust/src/item_data.rs")
+        .contains("pub struct ItemData"));
+    assert!(project
+        .read("rust/src/item_data.rs")
+        .contains("#[inherit(Resource)]"));
+    assert!(project
+        .read("rust/src/item_data.rs")
+        .contains("damage: i32,"));
+    assert!(project
+        .read("godot/native/ItemData.gdns")
+        .contains("class_name = \"ItemData\""));
+    assert!(project
+        .read("godot/resources/ItemData.tres")
+        .contains("icon = null"));
+    assert!(project.read("rust/src/lib.rs").contains("mod item_data;"));
+    assert!(project
+        .read("rust/src/lib.rs")
+        .contains("handle.add_class::<item_data::ItemData>();"));
+}
+
+#[test]
+fn test_ftw_resource_invalid_prop() {
+    let project = Project::new();
+    ftw()
+        .arg("new")
+        .arg(project.get_name())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("SUCCESS").from_utf8());
+    ftw()
+        .arg("resource")
+        .arg("ItemData")
+        .arg("--prop")
+        .arg("damage:u128")
+        .current_dir(project.get_name())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("error").from_utf8());
+    assert!(!project.exists("rust/src/item_data.rs"));
+}
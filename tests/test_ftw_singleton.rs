@@ -3,7 +3,6 @@ mod common;
 use assert_cmd::prelude::*;
 use common::ftw;
 use ftw::test_util::Project;
-use predicates;
 use predicates::prelude::*;
 
 #[test]
@@ -11,14 +10,14 @@ fn test_ftw_singleton() {
     let project = Project::new();
     ftw()
         .arg("new")
-        .arg(&project.get_name())
+        .arg(project.get_name())
         .assert()
         .success()
         .stdout(predicates::str::contains("SUCCESS").from_utf8());
     ftw()
         .arg("singleton")
         .arg("MyPlayer")
-        .current_dir(&project.get_name())
+        .current_dir(project.get_name())
         .assert()
         .success();
     assert!(project.exists("rust/src/my_player.rs"));
@@ -47,13 +46,13 @@ fn test_ftw_singleton_no_class_name() {
     let project = Project::new();
     ftw()
         .arg("new")
-        .arg(&project.get_name())
+        .arg(project.get_name())
         .assert()
         .success()
         .stdout(predicates::str::contains("SUCCESS").from_utf8());
     ftw()
         .arg("singleton")
-        .current_dir(&project.get_name())
+        .current_dir(project.get_name())
         .assert()
         .failure()
         .stderr(predicates::str::contains("error").from_utf8());
@@ -65,14 +64,14 @@ fn test_ftw_singleton_with_subs() {
     let project = Project::new();
     ftw()
         .arg("new")
-        .arg(&project.get_name())
+        .arg(project.get_name())
         .assert()
         .success()
         .stdout(predicates::str::contains("SUCCESS").from_utf8());
     ftw()
         .arg("singleton")
         .arg("foo/bar/baz/MyPlayer")
-        .current_dir(&project.get_name())
+        .current_dir(project.get_name())
         .assert()
         .success();
     assert!(project.exists("rust/src/foo/bar/baz/my_player.rs"));
@@ -108,7 +107,7 @@ fn test_ftw_singleton_with_subs() {
     ftw()
         .arg("singleton")
         .arg("foo/bar/FooBar")
-        .current_dir(&project.get_name())
+        .current_dir(project.get_name())
         .assert()
         .success();
     assert!(project.exists("rust/src/foo/bar/foo_bar.rs"));
@@ -143,7 +142,7 @@ fn test_ftw_singleton_with_subs() {
     ftw()
         .arg("singleton")
         .arg("foo/bar/baz/woot/Blah")
-        .current_dir(&project.get_name())
+        .current_dir(project.get_name())
         .assert()
         .success();
     assert!(project.exists("rust/src/foo/bar/baz/woot/blah.rs"));